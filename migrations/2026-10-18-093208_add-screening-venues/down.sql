@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE screenings DROP COLUMN venue_id;
+DROP TABLE venues;
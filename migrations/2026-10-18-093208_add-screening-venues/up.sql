@@ -0,0 +1,9 @@
+-- Your SQL goes here
+CREATE TABLE venues
+(
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL UNIQUE
+);
+
+--- screenings.location keeps the normalised venue name, venue_id references it
+ALTER TABLE screenings ADD COLUMN venue_id INTEGER REFERENCES venues(id) ON DELETE SET NULL;
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE screenings ADD COLUMN end_time TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE plays DROP COLUMN content_warnings;
+ALTER TABLE plays DROP COLUMN age_recommendation;
+ALTER TABLE plays DROP COLUMN surtitles;
+ALTER TABLE plays DROP COLUMN language;
+ALTER TABLE plays DROP COLUMN intermission;
+ALTER TABLE plays DROP COLUMN duration_minutes;
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+--- Structured fields parsed from the meta info of the play page, meta_info
+--- keeps the lines that could not be assigned to any of them.
+ALTER TABLE plays ADD COLUMN duration_minutes INTEGER;
+ALTER TABLE plays ADD COLUMN intermission BOOLEAN;
+ALTER TABLE plays ADD COLUMN language VARCHAR;
+ALTER TABLE plays ADD COLUMN surtitles VARCHAR;
+ALTER TABLE plays ADD COLUMN age_recommendation VARCHAR;
+ALTER TABLE plays ADD COLUMN content_warnings VARCHAR;
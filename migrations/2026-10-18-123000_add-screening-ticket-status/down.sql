@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+UPDATE screenings SET ticket_url = 'Ausverkauft' WHERE ticket_status = 'sold_out';
+ALTER TABLE screenings DROP COLUMN presale_date;
+ALTER TABLE screenings DROP COLUMN ticket_status;
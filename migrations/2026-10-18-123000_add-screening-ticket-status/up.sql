@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE screenings ADD COLUMN ticket_status VARCHAR NOT NULL DEFAULT 'available';
+ALTER TABLE screenings ADD COLUMN presale_date DATE;
+-- ticket_url used to be set to 'Ausverkauft' for sold out screenings
+UPDATE screenings SET ticket_status = 'sold_out', ticket_url = '' WHERE ticket_url = 'Ausverkauft';
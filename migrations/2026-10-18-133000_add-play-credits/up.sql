@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE persons (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL UNIQUE,
+    url VARCHAR
+);
+
+CREATE TABLE play_credits (
+    play_id INTEGER NOT NULL REFERENCES plays(id) ON DELETE CASCADE,
+    person_id INTEGER NOT NULL REFERENCES persons(id) ON DELETE CASCADE,
+    role VARCHAR NOT NULL,
+    -- order of the credit on the play page
+    position INTEGER NOT NULL,
+    PRIMARY KEY (play_id, person_id, role)
+);
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- chat_id is the private chat with the user, it is not a row in chats
+CREATE TABLE follows (
+    chat_id BIGINT NOT NULL,
+    person_id INTEGER NOT NULL REFERENCES persons(id) ON DELETE CASCADE,
+    PRIMARY KEY (chat_id, person_id)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE chats DROP COLUMN language;
+DROP TABLE play_translations;
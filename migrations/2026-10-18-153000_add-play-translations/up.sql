@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE play_translations (
+    play_id INTEGER NOT NULL REFERENCES plays(id) ON DELETE CASCADE,
+    language VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    description VARCHAR NOT NULL,
+    PRIMARY KEY (play_id, language)
+);
+
+ALTER TABLE chats ADD COLUMN language VARCHAR NOT NULL DEFAULT 'de';
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE plays ADD COLUMN content_hash VARCHAR;
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE screenings ADD COLUMN removed_at TIMESTAMPTZ;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE chats DROP COLUMN hide_archived;
+ALTER TABLE topics DROP COLUMN closed;
+ALTER TABLE plays DROP COLUMN archived_at;
+ALTER TABLE plays DROP COLUMN last_seen_at;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE plays ADD COLUMN last_seen_at TIMESTAMPTZ;
+ALTER TABLE plays ADD COLUMN archived_at TIMESTAMPTZ;
+ALTER TABLE topics ADD COLUMN closed BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE chats ADD COLUMN hide_archived BOOLEAN NOT NULL DEFAULT FALSE;
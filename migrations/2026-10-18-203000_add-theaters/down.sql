@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE chat_theaters;
+ALTER TABLE plays DROP CONSTRAINT plays_theater_id_url_key;
+ALTER TABLE plays ADD CONSTRAINT plays_url_key UNIQUE (url);
+ALTER TABLE plays DROP COLUMN theater_id;
+DROP TABLE theaters;
@@ -0,0 +1,26 @@
+-- Your SQL goes here
+CREATE TABLE theaters (
+    id SERIAL PRIMARY KEY,
+    slug VARCHAR NOT NULL UNIQUE,
+    name VARCHAR NOT NULL,
+    base_url VARCHAR NOT NULL
+);
+
+-- all existing plays are from the Schauspielhaus
+INSERT INTO theaters (slug, name, base_url)
+VALUES ('schauspielhaus', 'Schauspielhaus Zürich', 'https://www.schauspielhaus.ch');
+
+ALTER TABLE plays ADD COLUMN theater_id INTEGER REFERENCES theaters(id);
+UPDATE plays SET theater_id = (SELECT id FROM theaters WHERE slug = 'schauspielhaus');
+ALTER TABLE plays ALTER COLUMN theater_id SET NOT NULL;
+
+-- play urls are paths on the website of the theater
+ALTER TABLE plays DROP CONSTRAINT plays_url_key;
+ALTER TABLE plays ADD CONSTRAINT plays_theater_id_url_key UNIQUE (theater_id, url);
+
+-- a chat without rows follows all theaters
+CREATE TABLE chat_theaters (
+    chat_id BIGINT NOT NULL REFERENCES chats(id) ON DELETE CASCADE,
+    theater_id INTEGER NOT NULL REFERENCES theaters(id) ON DELETE CASCADE,
+    PRIMARY KEY (chat_id, theater_id)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE play_translations DROP COLUMN description_html;
+ALTER TABLE plays DROP COLUMN description_html;
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+--- The HTML of the description paragraphs, to keep emphasis, links and line
+--- breaks when formatting it for Telegram. description keeps the plain text.
+ALTER TABLE plays ADD COLUMN description_html VARCHAR NOT NULL DEFAULT '';
+ALTER TABLE play_translations ADD COLUMN description_html VARCHAR NOT NULL DEFAULT '';
+
+--- Scrape all plays again to fill in the HTML.
+UPDATE plays SET content_hash = NULL;
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+--- The kind of event (production, guest performance, talk, ...) as inferred
+--- from the calendar item of the play.
+ALTER TABLE plays ADD COLUMN category VARCHAR NOT NULL DEFAULT 'production';
+
+--- The categories of plays that get a topic in the chat.
+ALTER TABLE chats ADD COLUMN topic_categories TEXT[] NOT NULL
+    DEFAULT '{production,guest_performance,talk,concert,workshop,young}';
+
+--- Scrape all plays again to fill in the category.
+UPDATE plays SET content_hash = NULL;
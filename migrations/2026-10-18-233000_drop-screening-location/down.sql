@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE screenings ADD COLUMN location VARCHAR NOT NULL DEFAULT '';
+
+UPDATE screenings SET location = venues.name
+FROM venues
+WHERE venues.id = screenings.venue_id;
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+--- the venue of a screening is read from the venues table
+INSERT INTO venues (name)
+SELECT DISTINCT location FROM screenings WHERE location <> ''
+ON CONFLICT (name) DO NOTHING;
+
+UPDATE screenings SET venue_id = venues.id
+FROM venues
+WHERE venues.name = screenings.location AND screenings.venue_id IS NULL;
+
+ALTER TABLE screenings DROP COLUMN location;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX persons_lower_name_key;
+ALTER TABLE persons ADD CONSTRAINT persons_name_key UNIQUE (name);
@@ -0,0 +1,27 @@
+-- Your SQL goes here
+--- persons whose names only differ in case are merged into the oldest one
+CREATE TEMPORARY TABLE person_merges AS
+SELECT persons.id AS old_id, kept.id AS new_id
+FROM persons
+JOIN (SELECT lower(name) AS name, min(id) AS id FROM persons GROUP BY lower(name)) kept
+ON lower(persons.name) = kept.name
+WHERE persons.id <> kept.id;
+
+INSERT INTO play_credits (play_id, person_id, role, position)
+SELECT play_credits.play_id, person_merges.new_id, play_credits.role, play_credits.position
+FROM play_credits
+JOIN person_merges ON play_credits.person_id = person_merges.old_id
+ON CONFLICT DO NOTHING;
+
+INSERT INTO follows (chat_id, person_id)
+SELECT follows.chat_id, person_merges.new_id
+FROM follows
+JOIN person_merges ON follows.person_id = person_merges.old_id
+ON CONFLICT DO NOTHING;
+
+DELETE FROM persons WHERE id IN (SELECT old_id FROM person_merges);
+DROP TABLE person_merges;
+
+--- names are matched ignoring case, see put_person
+ALTER TABLE persons DROP CONSTRAINT persons_name_key;
+CREATE UNIQUE INDEX persons_lower_name_key ON persons (lower(name));
@@ -1,6 +1,8 @@
 pub mod models;
+pub mod report;
 pub mod schema;
 pub mod scrape;
+pub mod telegram;
 
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
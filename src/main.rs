@@ -1,45 +1,87 @@
+use std::collections::HashMap;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::path::Path;
+use std::path::PathBuf;
 
 use anyhow::Context;
+use chrono::Datelike;
 use clap::Parser;
 use clap::Subcommand;
-use diesel::update;
 use diesel::PgConnection;
 use dotenvy::dotenv;
-use env_logger;
 use log::debug;
 use log::error;
 use log::info;
-use log::LevelFilter;
 use rand::seq::SliceRandom;
-use rand::Rng;
 use schauspielhaus::establish_connection;
-use schauspielhaus::models::create_play_with_screenings;
+use schauspielhaus::models::credits_summary;
+use schauspielhaus::models::delete_follow;
+use schauspielhaus::models::find_person;
 use schauspielhaus::models::get_chat;
+use schauspielhaus::models::get_chat_theaters;
 use schauspielhaus::models::get_chats;
+use schauspielhaus::models::get_content_hashes;
+use schauspielhaus::models::get_followed_persons;
+use schauspielhaus::models::get_followers;
+use schauspielhaus::models::get_image_urls;
 use schauspielhaus::models::get_play_for_topic;
 use schauspielhaus::models::get_plays_and_topics;
 use schauspielhaus::models::get_plays_without_topic;
+use schauspielhaus::models::get_theater;
+use schauspielhaus::models::get_theaters;
 use schauspielhaus::models::put_chat;
+use schauspielhaus::models::put_follow;
+use schauspielhaus::models::put_person;
+use schauspielhaus::models::put_theater;
 use schauspielhaus::models::put_topic;
+use schauspielhaus::models::set_chat_hide_archived;
+use schauspielhaus::models::set_chat_language;
+use schauspielhaus::models::set_chat_theaters;
+use schauspielhaus::models::set_chat_topic_categories;
 use schauspielhaus::models::to_zurich_time;
 use schauspielhaus::models::Chat;
+use schauspielhaus::models::EventCategories;
+use schauspielhaus::models::EventCategory;
+use schauspielhaus::models::Language;
+use schauspielhaus::models::NewTheater;
 use schauspielhaus::models::PlayAndTopic;
 use schauspielhaus::models::PlayWithScreenings;
 use schauspielhaus::models::Screening;
+use schauspielhaus::models::ScreeningTag;
+use schauspielhaus::models::Theater;
+use schauspielhaus::models::TicketStatus;
 use schauspielhaus::models::Topic;
+use schauspielhaus::report::sync_plays;
+use schauspielhaus::report::Anomaly;
+use schauspielhaus::report::PlayChanges;
+use schauspielhaus::report::ScrapeReport;
+use schauspielhaus::scrape::failure_summary;
+use schauspielhaus::scrape::mirrored_image;
+use schauspielhaus::scrape::FetchOptions;
+use schauspielhaus::scrape::HttpFetcher;
+use schauspielhaus::scrape::ReplayFetcher;
+use schauspielhaus::scrape::Scraper;
+use schauspielhaus::scrape::ScraperBuilder;
+use schauspielhaus::scrape::Source;
+use schauspielhaus::scrape::TheaterSource;
+use schauspielhaus::scrape::DEFAULT_CONCURRENCY;
+use schauspielhaus::scrape::DEFAULT_HORIZON_MONTHS;
+use schauspielhaus::scrape::DEFAULT_REQUEST_DELAY;
+use schauspielhaus::scrape::DEFAULT_RETRIES;
+use schauspielhaus::scrape::DEFAULT_TIMEOUT;
+use schauspielhaus::scrape::DEFAULT_USER_AGENT;
+use schauspielhaus::telegram::html_to_markdown;
 use teloxide::adaptors::throttle::Limits;
 use teloxide::adaptors::Throttle;
 use teloxide::payloads::SendPollSetters;
-use teloxide::types::Me;
+use teloxide::types::InputFile;
 use teloxide::types::ParseMode;
 use teloxide::utils::markdown;
 use teloxide::ApiError;
 use teloxide::RequestError;
 use teloxide::{prelude::*, types::ChatKind, utils::command::BotCommands};
 
-use time::macros::format_description;
 use time::OffsetDateTime;
 use tokio::task;
 use tokio::time::{sleep, Duration};
@@ -50,13 +92,128 @@ use tokio::time::{sleep, Duration};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = DEFAULT_HORIZON_MONTHS,
+        help = "Number of months of the calendar to scrape"
+    )]
+    horizon_months: u32,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = DEFAULT_CONCURRENCY,
+        help = "Number of pages to download at the same time"
+    )]
+    concurrency: usize,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = DEFAULT_REQUEST_DELAY.as_millis() as u64,
+        help = "Minimum time between two requests to the website in milliseconds"
+    )]
+    request_delay_ms: u64,
+    #[arg(
+        long,
+        global = true,
+        default_value = DEFAULT_USER_AGENT,
+        help = "User-Agent header sent to the website"
+    )]
+    user_agent: String,
+    #[arg(
+        long,
+        global = true,
+        default_value_os_t = std::env::temp_dir().join("schauspielhaus-cache"),
+        help = "Directory to cache the downloaded pages in"
+    )]
+    cache_dir: PathBuf,
+    #[arg(long, global = true, help = "Download all pages without the cache")]
+    no_cache: bool,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = DEFAULT_TIMEOUT.as_secs(),
+        help = "Time after which a request to the website is given up in seconds"
+    )]
+    timeout_secs: u64,
+    #[arg(
+        long,
+        global = true,
+        default_value_t = DEFAULT_RETRIES,
+        help = "Number of times a request is retried after a timeout or server error"
+    )]
+    retries: u32,
+    #[arg(
+        long,
+        global = true,
+        default_value_os_t = std::env::temp_dir().join("schauspielhaus-images"),
+        help = "Directory to store the play images and their thumbnails in"
+    )]
+    image_dir: PathBuf,
+    #[arg(
+        long,
+        global = true,
+        help = "Scrape only the theater with this slug, e.g. schauspielhaus, instead of all of them"
+    )]
+    theater: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Scrape a copy of the theater website at this URL, e.g. a local mirror, together with --theater"
+    )]
+    base_url: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        default_value = "en",
+        help = "Language the plays are translated into, de scrapes only the German pages"
+    )]
+    language: Language,
+}
+
+impl Cli {
+    // sources returns the theaters to scrape, all of them unless --theater
+    // chooses one.
+    fn sources(&self) -> Vec<Source> {
+        match &self.theater {
+            Some(slug) => Source::from_slug(slug).into_iter().collect(),
+            None => Source::ALL.to_vec(),
+        }
+    }
+
+    // scraper returns the builder of the scraper for a theater configured by
+    // the flags.
+    fn scraper<S: TheaterSource>(&self, source: S) -> ScraperBuilder<S> {
+        let mut builder = Scraper::builder(source)
+            .fetch_options(FetchOptions {
+                concurrency: self.concurrency,
+                delay: Duration::from_millis(self.request_delay_ms),
+                user_agent: self.user_agent.clone(),
+                cache_dir: (!self.no_cache).then(|| self.cache_dir.clone()),
+                timeout: Duration::from_secs(self.timeout_secs),
+                retries: self.retries,
+                ..FetchOptions::default()
+            })
+            .language(self.language)
+            .horizon_months(self.horizon_months);
+        if let Some(base_url) = &self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        builder
+    }
 }
 
 #[derive(Subcommand)]
 enum Commands {
     // Command to start the bot
     #[command(about = "Start the bot")]
-    Start,
+    Start {
+        #[arg(
+            long,
+            help = "Telegram chat that is alerted when the scraped plays look broken"
+        )]
+        admin_chat_id: Option<i64>,
+    },
     // Command to scrape the schauspielhaus website
     #[command(about = "Scrape the schauspielhaus website")]
     Scrape,
@@ -66,6 +223,29 @@ enum Commands {
     // List all chats in the database
     #[command(about = "List chats in the database")]
     ListChats,
+    // Download the website into a directory that the tests can replay
+    #[command(about = "Record the calendar, play pages and screening ICS files into a directory")]
+    RecordFixtures {
+        #[arg(help = "Directory to store the recorded pages in")]
+        dir: PathBuf,
+    },
+    // Parse a play page from disk, for debugging the selectors
+    #[command(about = "Parse a local play page and print the play with its screenings as JSON")]
+    ParseFile {
+        #[arg(help = "Path to the play page")]
+        file: PathBuf,
+        #[arg(
+            long,
+            default_value = "",
+            help = "URL path of the play, e.g. /de/kalender/30546/liebe-einfach-ausserirdisch"
+        )]
+        url: String,
+        #[arg(
+            long,
+            help = "Load the screening ICS files from a fixture directory instead of the website"
+        )]
+        fixtures: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -75,15 +255,27 @@ async fn main() {
     env_logger::init_from_env(env);
 
     let cli = Cli::parse();
+    let sources = cli.sources();
+    let Some(&first) = sources.first() else {
+        error!("Unknown theater {:?}", cli.theater.unwrap_or_default());
+        return;
+    };
+    // the commands for a single website use the first theater
+    let scraper = cli.scraper(first);
 
     match cli.command {
-        Commands::Start => {
-            start_bot().await;
+        Commands::Start { admin_chat_id } => {
+            let scrapers = sources.iter().map(|s| cli.scraper(*s).build()).collect();
+            start_bot(scrapers, admin_chat_id.map(ChatId), cli.image_dir).await;
         }
         Commands::Scrape => {
             info!("establish database connection");
             let connection = &mut establish_connection();
-            update_plays(connection).await;
+            for source in &sources {
+                let scraper = cli.scraper(*source).build();
+                let report = update_plays(connection, &scraper, &cli.image_dir).await;
+                print!("{}", report);
+            }
         }
         Commands::List => task::spawn_blocking(|| {
             let connection = &mut establish_connection();
@@ -106,35 +298,180 @@ async fn main() {
         })
         .await
         .unwrap(),
+        Commands::RecordFixtures { dir } => match scraper.build().record_fixtures(&dir).await {
+            Ok(n) => info!("Recorded {} plays into {}", n, dir.display()),
+            Err(e) => error!("Error recording fixtures: {:?}", e),
+        },
+        Commands::ParseFile {
+            file,
+            url,
+            fixtures,
+        } => {
+            let content = std::fs::read_to_string(&file).expect("Error reading play page");
+            let (play, failures) = match fixtures {
+                Some(dir) => {
+                    scraper
+                        .build_with_fetcher(ReplayFetcher::new(dir))
+                        .play_from_page(&url, &content)
+                        .await
+                }
+                None => scraper.build().play_from_page(&url, &content).await,
+            };
+            if !failures.is_empty() {
+                error!("{}", failure_summary(&failures));
+            }
+            println!("{}", serde_json::to_string_pretty(&play).unwrap());
+        }
     }
 }
 
-async fn start_bot() {
+// start_bot answers the commands of the chats and scrapes the theaters of
+// `scrapers` periodically.
+async fn start_bot(
+    scrapers: Vec<Scraper<Source, HttpFetcher>>,
+    admin_chat_id: Option<ChatId>,
+    image_dir: PathBuf,
+) {
     log::info!("Starting schauspielhaus bot...");
     let bot = Bot::from_env().throttle(Limits::default());
 
+    let answer_image_dir = image_dir.clone();
+    let handler = move |bot: Throttle<Bot>, msg: Message, cmd: Command| {
+        answer(bot, msg, cmd, answer_image_dir.clone())
+    };
     // await both futures concurrently
     tokio::select! {
-        _ = Command::repl(bot.clone(), answer) => {},
-       _ = run_sync_function_periodically(&bot) => {},
+        _ = Command::repl(bot.clone(), handler) => {},
+       _ = run_sync_function_periodically(&bot, &scrapers, admin_chat_id, &image_dir) => {},
     }
 }
 
-// update_plays fetches the most recent plays from schauspielhaus and updates the database state.
-async fn update_plays(mut connection: &mut PgConnection) {
-    match schauspielhaus::scrape::get_plays().await {
-        Ok(plays) => {
-            info!("Found {} plays, inserting", plays.len());
-            for (_url, play) in plays {
-                create_play_with_screenings(&mut connection, play).expect("Error creating play");
+// update_plays scrapes the plays of the theater and updates the database
+// state, then mirrors the play images into a directory of the theater in
+// `image_dir`. It returns what changed.
+async fn update_plays(
+    connection: &mut PgConnection,
+    scraper: &Scraper<impl TheaterSource, HttpFetcher>,
+    image_dir: &Path,
+) -> ScrapeReport {
+    let source = scraper.source();
+    let theater = put_theater(
+        connection,
+        NewTheater {
+            slug: source.slug(),
+            name: source.name(),
+            base_url: source.base_url(),
+        },
+    )
+    .expect("Error saving theater");
+    // plays whose page didn't change since the last run are not updated
+    let known_hashes =
+        get_content_hashes(connection, theater.id).expect("Error getting content hashes");
+    match scraper.changed_plays(&known_hashes).await {
+        Ok(scraped) => {
+            info!(
+                "Found {} changed and {} unchanged plays, inserting",
+                scraped.plays.len(),
+                scraped.unchanged.len()
+            );
+            if !scraped.failures.is_empty() {
+                error!("{}", failure_summary(&scraped.failures));
             }
+            let report = sync_plays(connection, &theater, scraped).expect("Error updating plays");
+            let image_urls =
+                get_image_urls(connection, theater.id).expect("Error getting image urls");
+            let failures = scraper
+                .mirror_images(
+                    &image_dir.join(&theater.slug),
+                    image_urls.iter().map(String::as_str),
+                )
+                .await;
+            if !failures.is_empty() {
+                error!("Error mirroring images: {}", failure_summary(&failures));
+            }
+            report
         }
         Err(e) => {
-            error!("Error getting plays: {}", e.to_string());
+            error!("Error getting plays of {}: {}", theater.name, e.to_string());
+            // the admin is alerted, nothing can be scraped without the calendar
+            ScrapeReport {
+                theater,
+                anomalies: vec![Anomaly::NoCalendar(e.to_string())],
+                ..ScrapeReport::default()
+            }
         }
     }
 }
 
+// notify_followers tells the followers of the persons in a play about new plays
+// and new screenings.
+async fn notify_followers(bot: &Throttle<Bot>, report: &ScrapeReport) {
+    let connection = &mut establish_connection();
+    let updates = report
+        .plays
+        .iter()
+        .filter(|p| p.new_play || !p.new_screenings.is_empty());
+    for update in updates {
+        let followers = match get_followers(connection, update.play.play.id) {
+            Ok(f) => f,
+            Err(e) => {
+                error!(
+                    "Error getting followers of play '{}': {}",
+                    update.play.play.name, e
+                );
+                continue;
+            }
+        };
+        for (chat_id, names) in followers {
+            // private chats have no settings, they get the default language
+            // like the replies to /follow
+            let language = get_chat(connection, chat_id)
+                .map(|c| c.language)
+                .unwrap_or_default();
+            if let Err(e) = bot
+                .send_message(
+                    ChatId(chat_id),
+                    follow_message(update, &names, &report.theater, language),
+                )
+                .parse_mode(ParseMode::MarkdownV2)
+                .await
+            {
+                error!("Error notifying follower {}: {}", chat_id, e);
+            }
+        }
+    }
+}
+
+// follow_message announces a play update to someone following `names`.
+fn follow_message(
+    update: &PlayChanges,
+    names: &[String],
+    theater: &Theater,
+    language: Language,
+) -> String {
+    let play = &update.play.play;
+    let reply = match update.new_play {
+        true => Reply::NewPlayWith,
+        false => Reply::NewScreeningsWith,
+    };
+    let header = format!("{} {}:", reply_text(reply, language), names.join(", "));
+    let mut message_text = format!(
+        "🔔 {}\n[*{}*]({}{})",
+        markdown::escape(&header),
+        markdown::escape(update.play.name(language)),
+        theater.base_url,
+        play.url,
+    );
+    for screening in &update.new_screenings {
+        message_text.push_str(&format!(
+            "\n\\- {} {}",
+            markdown::escape(&option(screening, language)),
+            ticket_str(screening, language),
+        ));
+    }
+    message_text
+}
+
 /// These commands are supported:
 #[derive(BotCommands, Clone)]
 #[command(
@@ -155,20 +492,192 @@ enum Command {
     #[command(description = "force recreate the topics for all plays.")]
     ForceRefresh,
     /// Start a poll for this play.
-    #[command(description = "(in a play topic) start a poll for this play.")]
-    Poll,
+    #[command(
+        description = "(in a play topic) start a poll for this play, optionally only for screenings with a tag, e.g. /poll surtitles."
+    )]
+    Poll(String),
     /// (re)Post the description of the play.
     #[command(description = "(in a play topic) repost the description of the play.")]
     Description,
+    /// Follow a person and get notified of their new plays.
+    #[command(
+        description = "(in a private chat) get notified of new plays with a person, or list the persons you follow."
+    )]
+    Follow(String),
+    /// Stop following a person.
+    #[command(description = "(in a private chat) stop following a person.")]
+    Unfollow(String),
+    /// Choose the language of the bot in this chat.
+    #[command(description = "choose the language of the play topics and replies: de or en.")]
+    Language(String),
+    /// Mark the closed topics of archived plays in their name.
+    #[command(
+        description = "mark the topics of plays that left the programme with 🗄️ when closing them: on or off."
+    )]
+    HideArchived(String),
+    /// Choose the theaters this chat follows.
+    #[command(
+        description = "list the theatres, or choose the ones this chat follows, e.g. /theaters schauspielhaus."
+    )]
+    Theaters(String),
+    /// Choose the categories of events that get a topic.
+    #[command(
+        description = "list the event categories, or choose the ones that get a topic, e.g. /categories production talk."
+    )]
+    Categories(String),
+}
+
+// Reply is a message of the bot that is sent in the language of the chat.
+// Private chats have no settings and get the default language. Error messages
+// are meant for the bot admin and stay English.
+#[derive(Clone, Copy)]
+enum Reply {
+    Help,
+    Welcome,
+    ChatNotFound,
+    UsePrivateChat,
+    ChatAdded,
+    TopicsCreated,
+    TopicsRefreshed,
+    UseInTopic,
+    NoPlayForTopic,
+    PollTitle,
+    PollUsage,
+    NoScreenings,
+    LanguageSet,
+    LanguageUsage,
+    HideArchivedOn,
+    HideArchivedOff,
+    HideArchivedUsage,
+    TheatersFollowed,
+    TheatersUsage,
+    CategoriesSet,
+    CategoriesUsage,
+    FollowingNobody,
+    Following,
+    Followed,
+    FollowedUnknown,
+    Unfollowed,
+    NotFollowed,
+    NewPlayWith,
+    NewScreeningsWith,
+}
+
+fn reply_text(reply: Reply, language: Language) -> &'static str {
+    match (reply, language) {
+        (Reply::Help, Language::De) => {
+            "Dieser Bot funktioniert nur in öffentlichen Supergruppen mit aktivierten Themen."
+        }
+        (Reply::Help, Language::En) => {
+            "This bot only works in public super groups with topics enabled."
+        }
+        (Reply::Welcome, Language::De) => "Los geht's!",
+        (Reply::Welcome, Language::En) => "Let's start the party!",
+        (Reply::ChatNotFound, Language::De) => {
+            "Chat nicht in der Datenbank gefunden, bitte zuerst /start verwenden"
+        }
+        (Reply::ChatNotFound, Language::En) => {
+            "Chat not found in database, please use /start first"
+        }
+        (Reply::UsePrivateChat, Language::De) => {
+            "Bitte sende mir diesen Befehl in einem privaten Chat"
+        }
+        (Reply::UsePrivateChat, Language::En) => "Please send me this command in a private chat",
+        (Reply::ChatAdded, Language::De) => "Chat zur Datenbank hinzugefügt",
+        (Reply::ChatAdded, Language::En) => "Chat added to database",
+        (Reply::TopicsCreated, Language::De) => "Themen erstellt",
+        (Reply::TopicsCreated, Language::En) => "Topics created",
+        (Reply::TopicsRefreshed, Language::De) => "Themen aktualisiert",
+        (Reply::TopicsRefreshed, Language::En) => "Topics refreshed",
+        (Reply::UseInTopic, Language::De) => "Bitte verwende diesen Befehl in einem Stück-Thema",
+        (Reply::UseInTopic, Language::En) => "Please use this command in a play topic",
+        (Reply::NoPlayForTopic, Language::De) => "Kein Stück zu diesem Thema gefunden.",
+        (Reply::NoPlayForTopic, Language::En) => "No play found for this topic.",
+        (Reply::PollTitle, Language::De) => "Wann sollen wir gehen?",
+        (Reply::PollTitle, Language::En) => "When should we go?",
+        (Reply::PollUsage, Language::De) => "Unbekannter Tag, bekannt sind:",
+        (Reply::PollUsage, Language::En) => "Unknown tag, the known tags are:",
+        (Reply::NoScreenings, Language::De) => "Keine kommenden Vorstellungen gefunden.",
+        (Reply::NoScreenings, Language::En) => "No upcoming screenings found.",
+        (Reply::LanguageSet, Language::De) => {
+            "Die Sprache ist jetzt Deutsch, /forcerefresh aktualisiert die Themen."
+        }
+        (Reply::LanguageSet, Language::En) => {
+            "The language is now English, /forcerefresh updates the topics."
+        }
+        (Reply::LanguageUsage, Language::De) => "Verwendung: /language de oder /language en",
+        (Reply::LanguageUsage, Language::En) => "Usage: /language de or /language en",
+        (Reply::HideArchivedOn, Language::De) => {
+            "Themen von Stücken, die nicht mehr gespielt werden, werden geschlossen und mit 🗄️ markiert."
+        }
+        (Reply::HideArchivedOn, Language::En) => {
+            "Topics of plays that left the programme are closed and marked with 🗄️."
+        }
+        (Reply::HideArchivedOff, Language::De) => {
+            "Themen von Stücken, die nicht mehr gespielt werden, werden geschlossen."
+        }
+        (Reply::HideArchivedOff, Language::En) => {
+            "Topics of plays that left the programme are closed."
+        }
+        (Reply::HideArchivedUsage, Language::De) => {
+            "Verwendung: /hidearchived on oder /hidearchived off"
+        }
+        (Reply::HideArchivedUsage, Language::En) => "Usage: /hidearchived on or /hidearchived off",
+        (Reply::TheatersFollowed, Language::De) => {
+            "Dieser Chat folgt jetzt diesen Theatern, /refresh aktualisiert die Themen:"
+        }
+        (Reply::TheatersFollowed, Language::En) => {
+            "This chat now follows these theatres, /refresh updates the topics:"
+        }
+        (Reply::TheatersUsage, Language::De) => "Unbekanntes Theater, bekannt sind:",
+        (Reply::TheatersUsage, Language::En) => "Unknown theatre, the known theatres are:",
+        (Reply::CategoriesSet, Language::De) => {
+            "Themen werden jetzt für diese Kategorien erstellt, /refresh aktualisiert die Themen:"
+        }
+        (Reply::CategoriesSet, Language::En) => {
+            "Topics are now created for these categories, /refresh updates the topics:"
+        }
+        (Reply::CategoriesUsage, Language::De) => "Unbekannte Kategorie, bekannt sind:",
+        (Reply::CategoriesUsage, Language::En) => "Unknown category, the known categories are:",
+        (Reply::FollowingNobody, Language::De) => {
+            "Du folgst noch niemandem, verwende /follow <Name>"
+        }
+        (Reply::FollowingNobody, Language::En) => "You don't follow anyone yet, use /follow <name>",
+        (Reply::Following, Language::De) => "Du folgst",
+        (Reply::Following, Language::En) => "You follow",
+        (Reply::Followed, Language::De) => "Du erhältst eine Nachricht bei neuen Stücken mit",
+        (Reply::Followed, Language::En) => "You will be notified of new plays with",
+        (Reply::FollowedUnknown, Language::De) => {
+            "Noch in keinem Stück, du erhältst eine Nachricht, sobald sich das ändert:"
+        }
+        (Reply::FollowedUnknown, Language::En) => {
+            "Not in any play yet, you will be notified when they are:"
+        }
+        (Reply::Unfollowed, Language::De) => "Du folgst nicht mehr",
+        (Reply::Unfollowed, Language::En) => "You no longer follow",
+        (Reply::NotFollowed, Language::De) => "Du folgst nicht",
+        (Reply::NotFollowed, Language::En) => "You don't follow",
+        (Reply::NewPlayWith, Language::De) => "Neues Stück mit",
+        (Reply::NewPlayWith, Language::En) => "New play with",
+        (Reply::NewScreeningsWith, Language::De) => "Neue Vorstellungen mit",
+        (Reply::NewScreeningsWith, Language::En) => "New screenings with",
+    }
 }
-const HELP: &str = r"This bot only works in public super groups with topics enabled.";
 
-async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResult<()> {
+async fn answer(
+    bot: Throttle<Bot>,
+    msg: Message,
+    cmd: Command,
+    image_dir: PathBuf,
+) -> ResponseResult<()> {
     debug!(
         "Received message: {:?} thread id: {:?} chat id: {:?}",
         msg, msg.thread_id, msg.chat.id
     );
-    let title = msg.chat.title().clone().unwrap_or("").to_string();
+    let title = msg.chat.title().unwrap_or("").to_string();
+    let language = get_chat(&mut establish_connection(), msg.chat.id.0)
+        .map(|c| c.language)
+        .unwrap_or_default();
     match cmd {
         Command::Help => {
             bot.send_message(msg.chat.id, Command::descriptions().to_string())
@@ -177,23 +686,26 @@ async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResul
         Command::Start => {
             match msg.chat.kind {
                 ChatKind::Private { .. } => {
-                    bot.send_message(msg.chat.id, HELP).await?;
+                    bot.send_message(msg.chat.id, reply_text(Reply::Help, language))
+                        .await?;
                     return Ok(());
                 }
                 ChatKind::Public(p) => match p.kind {
                     teloxide::types::PublicChatKind::Supergroup(s) => {
                         if !s.is_forum {
-                            bot.send_message(msg.chat.id, HELP).await?;
+                            bot.send_message(msg.chat.id, reply_text(Reply::Help, language))
+                                .await?;
                             return Ok(());
                         }
                     }
                     _ => {
-                        bot.send_message(msg.chat.id, "HELP").await?;
+                        bot.send_message(msg.chat.id, reply_text(Reply::Help, language))
+                            .await?;
                         return Ok(());
                     }
                 },
             }
-            bot.send_message(msg.chat.id, "Let's start the party!")
+            bot.send_message(msg.chat.id, reply_text(Reply::Welcome, language))
                 .await
                 .expect("Sending welcome message failed");
 
@@ -204,12 +716,15 @@ async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResul
                 Chat {
                     id: msg.chat.id.0,
                     name: title,
+                    language,
+                    hide_archived: false,
+                    topic_categories: Default::default(),
                 },
             );
 
             match res {
                 Ok(_) => {
-                    bot.send_message(msg.chat.id, "Chat added to database")
+                    bot.send_message(msg.chat.id, reply_text(Reply::ChatAdded, language))
                         .await
                         .expect("Error sending message");
                 }
@@ -221,9 +736,9 @@ async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResul
                     return Ok(());
                 }
             }
-            match refresh_topics(&bot, msg.chat.id, false).await {
+            match refresh_topics(&bot, msg.chat.id, false, &image_dir).await {
                 Ok(_) => {
-                    bot.send_message(msg.chat.id, "Topics created")
+                    bot.send_message(msg.chat.id, reply_text(Reply::TopicsCreated, language))
                         .await
                         .expect("Error sending message");
                 }
@@ -243,12 +758,12 @@ async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResul
             return Ok(());
         }
         Command::Refresh => {
-            if !ensure_chat_exists(&bot, msg.chat.id).await {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
                 return Ok(());
             }
-            match refresh_topics(&bot, msg.chat.id, false).await {
+            match refresh_topics(&bot, msg.chat.id, false, &image_dir).await {
                 Ok(_) => {
-                    bot.send_message(msg.chat.id, "Topics refreshed")
+                    bot.send_message(msg.chat.id, reply_text(Reply::TopicsRefreshed, language))
                         .await
                         .expect("Error sending message");
                 }
@@ -261,12 +776,12 @@ async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResul
             return Ok(());
         }
         Command::ForceRefresh => {
-            if !ensure_chat_exists(&bot, msg.chat.id).await {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
                 return Ok(());
             }
-            match refresh_topics(&bot, msg.chat.id, true).await {
+            match refresh_topics(&bot, msg.chat.id, true, &image_dir).await {
                 Ok(_) => {
-                    bot.send_message(msg.chat.id, "Topics refreshed")
+                    bot.send_message(msg.chat.id, reply_text(Reply::TopicsRefreshed, language))
                         .await
                         .expect("Error sending message");
                 }
@@ -278,43 +793,174 @@ async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResul
             }
             return Ok(());
         }
-        Command::Poll => {
-            if !ensure_chat_exists(&bot, msg.chat.id).await {
+        Command::Poll(tag) => {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
                 return Ok(());
             }
+            let tag = match tag.trim() {
+                "" => None,
+                t => match t.parse::<ScreeningTag>() {
+                    Ok(tag) => Some(tag),
+                    Err(_) => {
+                        let tags = ScreeningTag::ALL
+                            .iter()
+                            .map(|t| t.as_str())
+                            .collect::<Vec<&str>>()
+                            .join(", ");
+                        bot.send_message(
+                            msg.chat.id,
+                            format!("{} {}", reply_text(Reply::PollUsage, language), tags),
+                        )
+                        .await?;
+                        return Ok(());
+                    }
+                },
+            };
             match msg.thread_id {
                 None => {
-                    bot.send_message(msg.chat.id, "Please use this command in a play topic")
+                    bot.send_message(msg.chat.id, reply_text(Reply::UseInTopic, language))
                         .await?;
                     return Ok(());
                 }
                 Some(topic_id) => {
-                    post_poll_for_topic(&bot, msg.chat.id, topic_id).await?;
+                    post_poll_for_topic(&bot, msg.chat.id, topic_id, language, tag).await?;
                 }
             }
             return Ok(());
         }
         Command::Description => {
-            if !ensure_chat_exists(&bot, msg.chat.id).await {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
                 return Ok(());
             }
             match msg.thread_id {
                 None => {
-                    bot.send_message(msg.chat.id, "Please use this command in a play topic")
+                    bot.send_message(msg.chat.id, reply_text(Reply::UseInTopic, language))
                         .await?;
                 }
-                Some(topic_id) => match post_description(&bot, msg.chat.id, topic_id).await {
-                    Ok(_) => {}
-                    Err(e) => {
-                        error!("Error posting description: {:?}", e);
-                        bot.send_message(
-                            msg.chat.id,
-                            format!("Error posting description: {:?}", e),
-                        )
-                        .await
-                        .map(|_| ())?;
+                Some(topic_id) => {
+                    match post_description(&bot, msg.chat.id, topic_id, language).await {
+                        Ok(_) => {}
+                        Err(e) => {
+                            error!("Error posting description: {:?}", e);
+                            bot.send_message(
+                                msg.chat.id,
+                                format!("Error posting description: {:?}", e),
+                            )
+                            .await
+                            .map(|_| ())?;
+                        }
                     }
-                },
+                }
+            }
+            return Ok(());
+        }
+        Command::Follow(_) | Command::Unfollow(_) if !msg.chat.is_private() => {
+            bot.send_message(msg.chat.id, reply_text(Reply::UsePrivateChat, language))
+                .await?;
+            return Ok(());
+        }
+        Command::Follow(name) => {
+            let text = match follow(msg.chat.id, name.trim(), language) {
+                Ok(t) => t,
+                Err(e) => {
+                    error!("Error following '{}' for {}: {}", name, msg.chat.id, e);
+                    format!("Error following {}: {}", name.trim(), e)
+                }
+            };
+            bot.send_message(msg.chat.id, text).await?;
+            return Ok(());
+        }
+        Command::Unfollow(name) => {
+            let text = match unfollow(msg.chat.id, name.trim(), language) {
+                Ok(t) => t,
+                Err(e) => {
+                    error!("Error unfollowing '{}' for {}: {}", name, msg.chat.id, e);
+                    format!("Error unfollowing {}: {}", name.trim(), e)
+                }
+            };
+            bot.send_message(msg.chat.id, text).await?;
+            return Ok(());
+        }
+        Command::Language(code) => {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
+                return Ok(());
+            }
+            let language = match code.trim().parse::<Language>() {
+                Ok(l) => l,
+                Err(_) => {
+                    bot.send_message(msg.chat.id, reply_text(Reply::LanguageUsage, language))
+                        .await?;
+                    return Ok(());
+                }
+            };
+            match set_chat_language(&mut establish_connection(), msg.chat.id.0, language) {
+                Ok(_) => {
+                    bot.send_message(msg.chat.id, reply_text(Reply::LanguageSet, language))
+                        .await?;
+                }
+                Err(e) => {
+                    error!("Error setting language of chat {}: {}", msg.chat.id.0, e);
+                    bot.send_message(msg.chat.id, format!("Error setting language: {}", e))
+                        .await?;
+                }
+            }
+            return Ok(());
+        }
+        Command::Theaters(slugs) => {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
+                return Ok(());
+            }
+            let text = match theaters(msg.chat.id, &slugs, language) {
+                Ok(t) => t,
+                Err(e) => {
+                    error!("Error choosing theaters of chat {}: {}", msg.chat.id.0, e);
+                    format!("Error choosing theaters: {}", e)
+                }
+            };
+            bot.send_message(msg.chat.id, text).await?;
+            return Ok(());
+        }
+        Command::Categories(names) => {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
+                return Ok(());
+            }
+            let text = match categories(msg.chat.id, &names, language) {
+                Ok(t) => t,
+                Err(e) => {
+                    error!("Error choosing categories of chat {}: {}", msg.chat.id.0, e);
+                    format!("Error choosing categories: {}", e)
+                }
+            };
+            bot.send_message(msg.chat.id, text).await?;
+            return Ok(());
+        }
+        Command::HideArchived(value) => {
+            if !ensure_chat_exists(&bot, msg.chat.id, language).await {
+                return Ok(());
+            }
+            let (hide_archived, reply) = match value.trim().to_lowercase().as_str() {
+                "on" => (true, Reply::HideArchivedOn),
+                "off" => (false, Reply::HideArchivedOff),
+                _ => {
+                    bot.send_message(msg.chat.id, reply_text(Reply::HideArchivedUsage, language))
+                        .await?;
+                    return Ok(());
+                }
+            };
+            match set_chat_hide_archived(&mut establish_connection(), msg.chat.id.0, hide_archived)
+            {
+                Ok(_) => {
+                    bot.send_message(msg.chat.id, reply_text(reply, language))
+                        .await?;
+                }
+                Err(e) => {
+                    error!(
+                        "Error setting hide_archived of chat {}: {}",
+                        msg.chat.id.0, e
+                    );
+                    bot.send_message(msg.chat.id, format!("Error saving setting: {}", e))
+                        .await?;
+                }
             }
             return Ok(());
         }
@@ -322,16 +968,187 @@ async fn answer(bot: Throttle<Bot>, msg: Message, cmd: Command) -> ResponseResul
     Ok(())
 }
 
+// theaters lets the chat follow the theaters with the given slugs and returns
+// the reply. Without slugs it lists all theaters, marking the followed ones.
+fn theaters(
+    msg_chat_id: ChatId,
+    slugs: &str,
+    language: Language,
+) -> Result<String, diesel::result::Error> {
+    let connection = &mut establish_connection();
+    let all = get_theaters(connection)?;
+    let list = |followed: &[i32]| {
+        all.iter()
+            .map(|t| {
+                let mark = if followed.contains(&t.id) {
+                    "✅"
+                } else {
+                    "▫️"
+                };
+                format!("{} {} ({})", mark, t.name, t.slug)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let slugs = slugs
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect::<Vec<String>>();
+    if slugs.is_empty() {
+        let followed = get_chat_theaters(connection, msg_chat_id.0)?
+            .iter()
+            .map(|t| t.id)
+            .collect::<Vec<i32>>();
+        return Ok(list(&followed));
+    }
+    let mut ids = vec![];
+    for slug in &slugs {
+        match all.iter().find(|t| &t.slug == slug) {
+            Some(t) => ids.push(t.id),
+            None => {
+                return Ok(format!(
+                    "{}\n{}",
+                    reply_text(Reply::TheatersUsage, language),
+                    list(&[])
+                ))
+            }
+        }
+    }
+    set_chat_theaters(connection, msg_chat_id.0, &ids)?;
+    Ok(format!(
+        "{}\n{}",
+        reply_text(Reply::TheatersFollowed, language),
+        list(&ids)
+    ))
+}
+
+// categories chooses the categories of events that get a topic in the chat
+// and returns the reply. Without names it lists all categories, marking the
+// chosen ones.
+fn categories(
+    msg_chat_id: ChatId,
+    names: &str,
+    language: Language,
+) -> Result<String, diesel::result::Error> {
+    let connection = &mut establish_connection();
+    let list = |chosen: &EventCategories| {
+        EventCategory::ALL
+            .iter()
+            .map(|c| {
+                let mark = if chosen.contains(*c) { "✅" } else { "▫️" };
+                format!(
+                    "{} {} {} ({})",
+                    mark,
+                    c.emoji(),
+                    c.label(language),
+                    c.as_str()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let names = names
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>();
+    if names.is_empty() {
+        let chat = get_chat(connection, msg_chat_id.0)?;
+        return Ok(list(&chat.topic_categories));
+    }
+    let mut chosen = EventCategories(vec![]);
+    for name in names {
+        match name.parse::<EventCategory>() {
+            Ok(c) if !chosen.contains(c) => chosen.0.push(c),
+            Ok(_) => {}
+            Err(_) => {
+                return Ok(format!(
+                    "{}\n{}",
+                    reply_text(Reply::CategoriesUsage, language),
+                    list(&EventCategories(vec![]))
+                ))
+            }
+        }
+    }
+    set_chat_topic_categories(connection, msg_chat_id.0, &chosen)?;
+    Ok(format!(
+        "{}\n{}",
+        reply_text(Reply::CategoriesSet, language),
+        list(&chosen)
+    ))
+}
+
+// follow lets the private chat follow the person called `name` and returns
+// the reply. Without a name it lists the persons that are followed.
+fn follow(
+    msg_chat_id: ChatId,
+    name: &str,
+    language: Language,
+) -> Result<String, diesel::result::Error> {
+    let connection = &mut establish_connection();
+    if name.is_empty() {
+        let persons = get_followed_persons(connection, msg_chat_id.0)?;
+        if persons.is_empty() {
+            return Ok(reply_text(Reply::FollowingNobody, language).to_string());
+        }
+        let names = persons
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect::<Vec<&str>>();
+        return Ok(format!(
+            "{} {}",
+            reply_text(Reply::Following, language),
+            names.join(", ")
+        ));
+    }
+    let (person, known) = match find_person(connection, name)? {
+        Some(p) => (p, true),
+        // remember the name, so that future credits match it
+        None => (put_person(connection, name, None)?, false),
+    };
+    put_follow(connection, msg_chat_id.0, person.id)?;
+    let reply = match known {
+        true => Reply::Followed,
+        false => Reply::FollowedUnknown,
+    };
+    Ok(format!("{} {}", reply_text(reply, language), person.name))
+}
+
+fn unfollow(
+    msg_chat_id: ChatId,
+    name: &str,
+    language: Language,
+) -> Result<String, diesel::result::Error> {
+    let connection = &mut establish_connection();
+    let person = match find_person(connection, name)? {
+        Some(p) => p,
+        None => {
+            return Ok(format!(
+                "{} {}",
+                reply_text(Reply::NotFollowed, language),
+                name
+            ))
+        }
+    };
+    let reply = match delete_follow(connection, msg_chat_id.0, person.id)? {
+        true => Reply::Unfollowed,
+        false => Reply::NotFollowed,
+    };
+    Ok(format!("{} {}", reply_text(reply, language), person.name))
+}
+
 async fn post_poll_for_topic(
     bot: &Throttle<Bot>,
     msg_chat_id: ChatId,
     topic_id: teloxide::types::ThreadId,
+    language: Language,
+    tag: Option<ScreeningTag>,
 ) -> Result<(), RequestError> {
     let connection = &mut establish_connection();
     let play_with_screenings = match get_play_for_topic(connection, topic_id.0 .0) {
         Ok(p) => p,
         Err(diesel::result::Error::NotFound) => {
-            bot.send_message(msg_chat_id, "No play found for this topic.")
+            bot.send_message(msg_chat_id, reply_text(Reply::NoPlayForTopic, language))
                 .message_thread_id(topic_id)
                 .await?;
             return Ok(());
@@ -350,53 +1167,69 @@ async fn post_poll_for_topic(
             return Ok(());
         }
     };
-    // only consider screenings in the future
+    // only consider screenings in the future that take place
     let now = OffsetDateTime::now_utc();
     let screenings = play_with_screenings
         .screenings
         .iter()
-        .filter(|s| s.start_time > now)
+        .filter(|s| s.start_time > now && s.takes_place())
+        .filter(|s| tag.is_none_or(|t| s.tags.contains(t)))
         .collect::<Vec<&Screening>>();
+    // a poll needs at least two options
+    if screenings.len() < 2 {
+        let text = match screenings.first() {
+            Some(s) => option(s, language),
+            None => reply_text(Reply::NoScreenings, language).to_string(),
+        };
+        bot.send_message(msg_chat_id, text)
+            .message_thread_id(topic_id)
+            .await?;
+        return Ok(());
+    }
     let total = screenings.len() / 10;
     for (i, chunk) in screenings.chunks(10).enumerate() {
         let title = match total > 0 {
-            true => format!("When should we go? {}/{}", i, total),
-            false => "When should we go?".to_string(),
+            true => format!("{} {}/{}", reply_text(Reply::PollTitle, language), i, total),
+            false => reply_text(Reply::PollTitle, language).to_string(),
         };
-        bot.send_poll(msg_chat_id, title, chunk.iter().map(|s| option(s)))
+        let options = chunk
+            .iter()
+            .map(|s| format!("{} {}", option(s, language), s.ticket_status.emoji()));
+        bot.send_poll(msg_chat_id, title, options)
             .message_thread_id(topic_id)
             .allows_multiple_answers(true)
             .is_anonymous(false)
             .await?;
     }
-    return Ok(());
+    Ok(())
 }
 
-fn option(s: &&Screening) -> String {
-    to_zurich_time(s.start_time)
-        .format("%A %d.%m.%Y %H:%M")
-        .to_string()
+fn option(s: &Screening, language: Language) -> String {
+    let weekday = language.weekday(to_zurich_time(s.start_time).weekday());
+    let start = format!("{} {}", weekday, s.time_range("%d.%m.%Y"));
+    let mut option = match s.venue() {
+        Some(venue) => format!("{}, {}", start, venue),
+        None => start,
+    };
+    for tag in s.tags.iter() {
+        option.push_str(&format!(" {} {}", tag.emoji(), tag.label(language)));
+    }
+    option
 }
 
-async fn ensure_chat_exists(bot: &Throttle<Bot>, msg_chat_id: ChatId) -> bool {
+async fn ensure_chat_exists(bot: &Throttle<Bot>, msg_chat_id: ChatId, language: Language) -> bool {
     let connection = &mut establish_connection();
     let chat = get_chat(connection, msg_chat_id.0);
     match chat {
-        Ok(_) => {
-            return true;
-        }
+        Ok(_) => true,
         Err(_) => {
             let _ = bot
-                .send_message(
-                    msg_chat_id,
-                    "Chat not found in database, please use /start first",
-                )
+                .send_message(msg_chat_id, reply_text(Reply::ChatNotFound, language))
                 .await
-                .map_err(|e| {
-                    error!("Error sending message to chat: {}", e.to_string());
-                    e
+                .inspect_err(|e| {
+                    error!("Error sending message to chat: {}", e);
                 });
-            return false;
+            false
         }
     }
 }
@@ -411,12 +1244,16 @@ async fn post_description(
     bot: &Throttle<Bot>,
     msg_chat_id: ChatId,
     topic_id: teloxide::types::ThreadId,
+    language: Language,
 ) -> Result<(), anyhow::Error> {
     let mut connection = establish_connection();
     let play_with_screenings = match get_play_for_topic(&mut connection, topic_id.0 .0) {
         Ok(p) => p,
         Err(diesel::result::Error::NotFound) => {
-            return Err(anyhow::Error::msg("No play found for this topic."));
+            return Err(anyhow::Error::msg(reply_text(
+                Reply::NoPlayForTopic,
+                language,
+            )));
         }
         Err(e) => {
             return Err(anyhow::Error::msg(format!(
@@ -425,13 +1262,10 @@ async fn post_description(
             )));
         }
     };
-    let message_text = pinned_message(&play_with_screenings.play, &play_with_screenings.screenings);
-    let message_hash = (|| {
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        message_text.hash(&mut hasher);
-        hasher.finish()
-    })();
-    let pinned_message_id = create_pinned_message(&bot, message_text, msg_chat_id, topic_id)
+    let theater = get_theater(&mut connection, play_with_screenings.play.theater_id)?;
+    let message_text = pinned_message(&play_with_screenings, &theater, language);
+    let message_hash = message_hash(&message_text);
+    let pinned_message_id = create_pinned_message(bot, message_text, msg_chat_id, topic_id)
         .await
         .with_context(|| {
             format!(
@@ -446,9 +1280,10 @@ async fn post_description(
             message_thread_id: topic_id.0 .0,
             play_id: play_with_screenings.play.id,
             chat_id: msg_chat_id.0,
-            pinned_message_id: pinned_message_id,
-            pinned_message_hash: message_hash as i64,
+            pinned_message_id,
+            pinned_message_hash: message_hash,
             last_updated: OffsetDateTime::now_utc(),
+            closed: play_with_screenings.play.archived_at.is_some(),
         },
     )
     .with_context(|| {
@@ -464,26 +1299,76 @@ async fn refresh_topics(
     bot: &Throttle<Bot>,
     msg_chat_id: ChatId,
     force: bool,
+    image_dir: &Path,
 ) -> Result<(), anyhow::Error> {
-    let mut connection = &mut establish_connection();
-    let plays = get_plays_and_topics(connection, msg_chat_id.0).map_err(|e| {
-        error!("Error getting plays: {}", e.to_string());
-        e
+    let connection = &mut establish_connection();
+    let chat = get_chat(connection, msg_chat_id.0)?;
+    let language = chat.language;
+    let plays = get_plays_and_topics(connection, msg_chat_id.0).inspect_err(|e| {
+        error!("Error getting plays: {}", e);
     })?;
     debug!("Found {} plays to refresh", plays.len());
+    let theaters = get_theaters(connection)?
+        .into_iter()
+        .map(|t| (t.id, t))
+        .collect::<HashMap<i32, Theater>>();
     // collect errors
     let mut errors = vec![];
     for PlayAndTopic {
-        play: PlayWithScreenings { play, screenings },
+        play: play_with_screenings,
         topic,
     } in plays
     {
+        let play = &play_with_screenings.play;
+        let theater = &theaters[&play.theater_id];
+        if play.archived_at.is_some() {
+            // archived plays get no new topic, existing ones are closed
+            if let Some(topic) = &topic {
+                let name = play_with_screenings.name(language);
+                if let Err(e) = archive_topic(bot, connection, &chat, topic, name).await {
+                    errors.push(anyhow::Error::msg(format!(
+                        "Error archiving topic for play '{}': {}",
+                        play.name, e
+                    )));
+                }
+            }
+            continue;
+        }
+        let mut reopened = false;
+        if let Some(t) = topic.as_ref().filter(|t| t.closed) {
+            let thread_id =
+                teloxide::types::ThreadId(teloxide::types::MessageId(t.message_thread_id));
+            if let Err(e) = bot.reopen_forum_topic(msg_chat_id, thread_id).await {
+                errors.push(anyhow::Error::msg(format!(
+                    "Error reopening topic for play '{}': {}",
+                    play.name, e
+                )));
+                continue;
+            }
+            // the name of the topic may have the archived marker
+            if let Err(e) = bot
+                .edit_forum_topic(msg_chat_id, thread_id)
+                .name(play_with_screenings.name(language))
+                .await
+            {
+                errors.push(anyhow::Error::msg(format!(
+                    "Error renaming topic for play '{}': {}",
+                    play.name, e
+                )));
+            }
+            reopened = true;
+        }
         let message_thread_id = match &topic {
             Some(t) => teloxide::types::ThreadId(teloxide::types::MessageId(t.message_thread_id)),
             None => {
                 // Create the forum topic
                 let t = match bot
-                    .create_forum_topic(msg_chat_id, &play.name, random_icon_color(), "")
+                    .create_forum_topic(
+                        msg_chat_id,
+                        play_with_screenings.name(language),
+                        random_icon_color(),
+                        "",
+                    )
                     .await
                 {
                     Ok(t) => t,
@@ -496,49 +1381,65 @@ async fn refresh_topics(
                     }
                 };
                 debug!("Created topic {} with ID {}", t.name, t.thread_id);
+                // the topic works without the photo, it is only logged
+                if let Err(e) = post_photo(
+                    bot,
+                    msg_chat_id,
+                    t.thread_id,
+                    &play_with_screenings,
+                    theater,
+                    language,
+                    &image_dir.join(&theater.slug),
+                )
+                .await
+                {
+                    error!("Error posting photo for play '{}': {}", play.name, e);
+                }
                 t.thread_id
             }
         };
         // Delete the existing pinned message
         let mut pinned_message_id = topic.as_ref().map_or(0, |t| t.pinned_message_id);
 
-        let message_text = pinned_message(&play, &screenings);
-        let message_hash = (|| {
-            let mut hasher = std::collections::hash_map::DefaultHasher::new();
-            message_text.hash(&mut hasher);
-            hasher.finish()
-        })();
-        if force || pinned_message_id == 0 {
-            pinned_message_id =
-                match create_pinned_message(&bot, message_text, msg_chat_id, message_thread_id)
-                    .await
-                {
-                    Ok(id) => id,
-                    Err(e) => {
-                        if let RequestError::Api(ApiError::Unknown(error)) = &e {
-                            if error == "Bad Request: message thread not found" {
-                                // ignore if the topic was deleted
-                            }
-                        } else {
-                            errors.push(anyhow::Error::msg(format!(
-                                "Error sending play info for play '{}': {}",
-                                play.name, e
-                            )));
+        let message_text = pinned_message(&play_with_screenings, theater, language);
+        let message_hash = message_hash(&message_text);
+        // a reopened topic gets a new pinned message with the new screenings
+        if force || reopened || pinned_message_id == 0 {
+            pinned_message_id = match create_pinned_message(
+                bot,
+                message_text,
+                msg_chat_id,
+                message_thread_id,
+            )
+            .await
+            {
+                Ok(id) => id,
+                Err(e) => {
+                    if let RequestError::Api(ApiError::Unknown(error)) = &e {
+                        if error == "Bad Request: message thread not found" {
+                            // ignore if the topic was deleted
                         }
-                        0
+                    } else {
+                        errors.push(anyhow::Error::msg(format!(
+                            "Error sending play info for play '{}': {}",
+                            play.name, e
+                        )));
                     }
-                };
+                    0
+                }
+            };
         }
 
         match put_topic(
-            &mut connection,
+            connection,
             Topic {
                 message_thread_id: message_thread_id.0 .0,
                 play_id: play.id,
                 chat_id: msg_chat_id.0,
-                pinned_message_id: pinned_message_id,
-                pinned_message_hash: message_hash as i64,
+                pinned_message_id,
+                pinned_message_hash: message_hash,
                 last_updated: OffsetDateTime::now_utc(),
+                closed: false,
             },
         ) {
             Ok(_) => {}
@@ -551,7 +1452,7 @@ async fn refresh_topics(
             }
         };
     }
-    if errors.len() > 0 {
+    if !errors.is_empty() {
         return Err(anyhow::Error::msg(format!(
             "Errors refreshing topics: {}",
             errors
@@ -564,10 +1465,128 @@ async fn refresh_topics(
     Ok(())
 }
 
+// MAX_CAPTION_LENGTH is the number of characters Telegram allows in the
+// caption of a photo.
+const MAX_CAPTION_LENGTH: usize = 1024;
+
+// post_photo posts the thumbnail of the play image in `image_dir` with the
+// title and description as caption. Nothing is posted if the image wasn't
+// mirrored.
+async fn post_photo(
+    bot: &Throttle<Bot>,
+    msg_chat_id: ChatId,
+    topic_id: teloxide::types::ThreadId,
+    play_with_screenings: &PlayWithScreenings,
+    theater: &Theater,
+    language: Language,
+    image_dir: &Path,
+) -> Result<(), RequestError> {
+    let image_url = &play_with_screenings.play.image_url;
+    if image_url.is_empty() {
+        return Ok(());
+    }
+    let thumbnail = mirrored_image(image_dir, image_url).thumbnail;
+    if !thumbnail.exists() {
+        debug!("No thumbnail for image {}", image_url);
+        return Ok(());
+    }
+    bot.send_photo(msg_chat_id, InputFile::file(thumbnail))
+        .caption(photo_caption(play_with_screenings, theater, language))
+        .parse_mode(ParseMode::MarkdownV2)
+        .message_thread_id(topic_id)
+        .await?;
+    Ok(())
+}
+
+// photo_caption is the linked title and the description of a play, the
+// description is shortened to fit into MAX_CAPTION_LENGTH.
+fn photo_caption(
+    play_with_screenings: &PlayWithScreenings,
+    theater: &Theater,
+    language: Language,
+) -> String {
+    let name = play_with_screenings.name(language);
+    let mut caption = format!(
+        "[*{}*]({}{})",
+        markdown::escape(name),
+        theater.base_url,
+        play_with_screenings.play.url
+    );
+    let description = play_with_screenings.description(language).trim();
+    if description.is_empty() {
+        return caption;
+    }
+    // only the visible text counts, not the markup
+    let available = MAX_CAPTION_LENGTH.saturating_sub(name.chars().count() + 2);
+    let description = if description.chars().count() > available {
+        let shortened = description
+            .chars()
+            .take(available.saturating_sub(1))
+            .collect::<String>();
+        format!("{}…", shortened.trim_end())
+    } else {
+        description.to_string()
+    };
+    caption.push_str(&format!("\n\n{}", markdown::escape(&description)));
+    caption
+}
+
+// ARCHIVED_MARKER is put in front of the name of the topics of archived plays
+// in chats that hide archived plays.
+const ARCHIVED_MARKER: &str = "🗄️ ";
+
+// archive_topic closes the topic of an archived play named `name`, and marks
+// its name if the chat hides archived plays. The topic is kept with its
+// messages, it is reopened if the play is listed again.
+async fn archive_topic(
+    bot: &Throttle<Bot>,
+    connection: &mut PgConnection,
+    chat: &Chat,
+    topic: &Topic,
+    name: &str,
+) -> Result<(), anyhow::Error> {
+    if topic.closed {
+        return Ok(());
+    }
+    let chat_id = ChatId(chat.id);
+    let thread_id = teloxide::types::ThreadId(teloxide::types::MessageId(topic.message_thread_id));
+    if chat.hide_archived {
+        bot.edit_forum_topic(chat_id, thread_id)
+            .name(format!("{}{}", ARCHIVED_MARKER, name))
+            .await?;
+    }
+    bot.close_forum_topic(chat_id, thread_id).await?;
+    put_topic(
+        connection,
+        Topic {
+            closed: true,
+            last_updated: OffsetDateTime::now_utc(),
+            ..topic.clone()
+        },
+    )?;
+    Ok(())
+}
+
+// description_markdown is the description of the play formatted from its
+// HTML, or the escaped plain text for plays scraped before the HTML was kept.
+fn description_markdown(
+    play_with_screenings: &PlayWithScreenings,
+    theater: &Theater,
+    language: Language,
+) -> String {
+    let html = play_with_screenings.description_html(language);
+    if html.trim().is_empty() {
+        return markdown::escape(play_with_screenings.description(language));
+    }
+    html_to_markdown(html, &theater.base_url)
+}
+
 fn pinned_message(
-    play: &schauspielhaus::models::Play,
-    screenings: &Vec<schauspielhaus::models::Screening>,
+    play_with_screenings: &PlayWithScreenings,
+    theater: &Theater,
+    language: Language,
 ) -> String {
+    let play = &play_with_screenings.play;
     let mut message_text = format!(
         "\
 [*{}*]({}{}) 🎭️
@@ -576,31 +1595,85 @@ fn pinned_message(
 
 {}
 ",
-        markdown::escape(&play.name),
-        schauspielhaus::scrape::BASE_URL,
+        markdown::escape(play_with_screenings.name(language)),
+        theater.base_url,
         play.url,
-        markdown::escape(&play.description),
-        markdown::escape(&play.meta_info),
+        description_markdown(play_with_screenings, theater, language),
+        markdown::escape(&play.meta_lines(language).join("\n")),
     );
-    if screenings.len() > 0 {
-        message_text.push_str("\n🎟️ *Screenings*:");
+    if let Some(summary) = credits_summary(&play_with_screenings.credits) {
+        message_text.push_str(&format!("\n{}\n", markdown::escape(&summary)));
     }
-    for screening in screenings {
-        let mut ticket_str = "".to_string();
-        if screening.ticket_url == "Ausverkauft" {
-            ticket_str = " (Ausverkauft)".to_string();
-        } else if screening.ticket_url != "" {
-            ticket_str = format!(" [Tickets]({})", screening.ticket_url);
-        }
-        message_text.push_str(&format!(
-            "\n\\- {}{}",
-            markdown::escape(&option(&screening)),
-            ticket_str,
-        ));
+    if !play_with_screenings.screenings.is_empty() {
+        message_text.push_str(match language {
+            Language::De => "\n🎟️ *Vorstellungen*:",
+            Language::En => "\n🎟️ *Screenings*:",
+        });
+    }
+    for screening in &play_with_screenings.screenings {
+        message_text.push_str(&format!("\n\\- {}", screening_line(screening, language)));
     }
     message_text
 }
 
+// screening_line is a screening in the pinned message, screenings that don't
+// take place are struck through.
+fn screening_line(screening: &Screening, language: Language) -> String {
+    let option = markdown::escape(&option(screening, language));
+    if screening.removed_at.is_some() {
+        let label = match language {
+            Language::De => "Nicht mehr im Spielplan",
+            Language::En => "No longer scheduled",
+        };
+        format!("~{}~ 🗑️ {}", option, markdown::escape(label))
+    } else if !screening.takes_place() {
+        format!("~{}~ {}", option, ticket_str(screening, language))
+    } else {
+        format!("{} {}", option, ticket_str(screening, language))
+    }
+}
+
+// ticket_str renders the ticket status of a screening, linking to the ticket
+// shop while tickets can be bought.
+fn ticket_str(screening: &Screening, language: Language) -> String {
+    let status = screening.ticket_status;
+    let label = match (status, screening.presale_date) {
+        (TicketStatus::Presale, Some(date)) => format!(
+            "{} {} {:02}.{:02}.{}",
+            status.label(language),
+            match language {
+                Language::De => "ab",
+                Language::En => "from",
+            },
+            date.day(),
+            u8::from(date.month()),
+            date.year()
+        ),
+        _ => status.label(language).to_string(),
+    };
+    let linked = matches!(
+        status,
+        TicketStatus::Available | TicketStatus::FewLeft | TicketStatus::Waitlist
+    );
+    if linked && !screening.ticket_url.is_empty() {
+        format!(
+            "{} [{}]({})",
+            status.emoji(),
+            markdown::escape(&label),
+            markdown::escape_link_url(&screening.ticket_url)
+        )
+    } else {
+        format!("{} {}", status.emoji(), markdown::escape(&label))
+    }
+}
+
+// message_hash is used to detect whether the pinned message of a topic is outdated.
+fn message_hash(message_text: &str) -> i64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    message_text.hash(&mut hasher);
+    hasher.finish() as i64
+}
+
 async fn create_pinned_message(
     bot: &Throttle<Bot>,
     message_text: String,
@@ -617,33 +1690,66 @@ async fn create_pinned_message(
     Ok(pinned_msg.id.0)
 }
 
-async fn run_sync_function_periodically(bot: &Throttle<Bot>) {
+// MAX_ALERT_LENGTH keeps an alert below the message size limit of Telegram.
+const MAX_ALERT_LENGTH: usize = 4000;
+
+// alert_admin sends a report that looks like the scraper is broken to the admin
+// chat, or only logs it if there is none.
+async fn alert_admin(bot: &Throttle<Bot>, admin_chat_id: Option<ChatId>, report: &ScrapeReport) {
+    error!("The scraper may be broken: {}", report);
+    let Some(chat_id) = admin_chat_id else {
+        return;
+    };
+    let text = format!("⚠️ The scraper may be broken:\n{}", report)
+        .chars()
+        .take(MAX_ALERT_LENGTH)
+        .collect::<String>();
+    if let Err(e) = bot.send_message(chat_id, text).await {
+        error!("Error alerting admin chat {}: {}", chat_id, e);
+    }
+}
+
+async fn run_sync_function_periodically(
+    bot: &Throttle<Bot>,
+    scrapers: &[Scraper<Source, HttpFetcher>],
+    admin_chat_id: Option<ChatId>,
+    image_dir: &Path,
+) {
     loop {
         info!("establish database connection");
         let connection = &mut establish_connection();
-        info!("fetch new plays from schauspielhaus website");
-        update_plays(connection).await;
+        for scraper in scrapers {
+            info!(
+                "fetch new plays from the {} website",
+                scraper.source().name()
+            );
+            let report = update_plays(connection, scraper, image_dir).await;
+            if !report.is_empty() {
+                info!("Scrape report: {}", report);
+            }
+            if report.needs_attention() {
+                alert_admin(bot, admin_chat_id, &report).await;
+            }
+            notify_followers(bot, &report).await;
+        }
         let chats = get_chats(&mut establish_connection()).unwrap();
         for chat in chats {
             let chat_id = teloxide::prelude::ChatId(chat.id);
-            match refresh_topics(bot, chat_id, false).await {
-                Err(e) => {
-                    match bot
-                        .send_message(chat_id, format!("Error refreshing topics: {}", e))
-                        .await
-                    {
-                        Ok(_) => {}
-                        // ignore if the chat was deleted
-                        Err(RequestError::Api(ApiError::ChatNotFound)) => {}
-                        Err(send_err) => {
-                            error!(
-                                "Error sending message to chat {}: {}, refresh_error: {}",
-                                chat.id, send_err, e
-                            );
-                        }
+            if let Err(e) = refresh_topics(bot, chat_id, false, image_dir).await {
+                match bot
+                    .send_message(chat_id, format!("Error refreshing topics: {}", e))
+                    .await
+                {
+                    Ok(_) => {}
+                    // ignore if the chat was deleted
+                    Err(RequestError::Api(ApiError::ChatNotFound)) => {}
+                    Err(send_err) => {
+                        error!(
+                            "Error sending message to chat {}: {}, refresh_error: {}",
+                            chat.id, send_err, e
+                        );
                     }
                 }
-                Ok(_) => (),
             }
         }
         sleep(Duration::from_secs(60 * 60 * 3)).await;
@@ -654,13 +1760,13 @@ async fn run_sync_function_periodically(bot: &Throttle<Bot>) {
 fn test_get_plays_and_topics() {
     let _ = env_logger::builder()
         .is_test(true)
-        .filter_level(LevelFilter::Debug)
+        .filter_level(log::LevelFilter::Debug)
         .try_init();
-    let mut connection = &mut establish_connection();
+    let connection = &mut establish_connection();
     println!("Fetching plays and topics");
-    let chats = get_chats(&mut connection).unwrap();
+    let chats = get_chats(connection).unwrap();
     for chat in chats {
-        let plays = get_plays_and_topics(&mut connection, chat.id).unwrap();
+        let plays = get_plays_and_topics(connection, chat.id).unwrap();
         for PlayAndTopic { play, topic } in plays {
             match topic {
                 Some(t) => println!("{}: {}", play.play.name, t.message_thread_id),
@@ -669,3 +1775,297 @@ fn test_get_plays_and_topics() {
         }
     }
 }
+
+#[test]
+fn test_follow_ignores_case() {
+    use diesel::prelude::*;
+    use schauspielhaus::models::create_play_with_screenings;
+    use schauspielhaus::models::Credit;
+    use schauspielhaus::schema::{persons, plays};
+
+    let connection = &mut establish_connection();
+    let url = "/de/kalender/0/test-follow-ignores-case";
+    let cleanup = |connection: &mut PgConnection| {
+        diesel::delete(plays::table.filter(plays::url.eq(url)))
+            .execute(connection)
+            .unwrap();
+        diesel::delete(persons::table.filter(persons::name.ilike("annatest müller")))
+            .execute(connection)
+            .unwrap();
+    };
+    cleanup(connection);
+
+    let chat_id = ChatId(1);
+    follow(chat_id, "annatest müller", Language::De).unwrap();
+    let mut play = PlayWithScreenings::default();
+    play.play.name = "Test".to_string();
+    play.play.url = url.to_string();
+    play.play.theater_id = get_theaters(connection).unwrap()[0].id;
+    play.credits = vec![Credit {
+        role: "Regie".to_string(),
+        name: "Annatest Müller".to_string(),
+        url: None,
+    }];
+    let play = create_play_with_screenings(connection, play, true).unwrap();
+    let followers = get_followers(connection, play.play.id);
+    cleanup(connection);
+    assert_eq!(
+        followers.unwrap(),
+        HashMap::from([(chat_id.0, vec!["Annatest Müller".to_string()])])
+    );
+}
+
+#[tokio::test]
+async fn test_keep_screenings_of_broken_rows() {
+    use diesel::prelude::*;
+    use schauspielhaus::models::create_play_with_screenings;
+    use schauspielhaus::schema::{plays, screenings};
+    use schauspielhaus::scrape::Schauspielhaus;
+
+    let connection = &mut establish_connection();
+    let url = "/de/kalender/0/test-keep-screenings-of-broken-rows";
+    let cleanup = |connection: &mut PgConnection| {
+        diesel::delete(plays::table.filter(plays::url.eq(url)))
+            .execute(connection)
+            .unwrap();
+    };
+    cleanup(connection);
+
+    // a play page with a screening row on each of the next two days
+    let row = |days: i64, id: u32| {
+        let date = chrono::Utc::now() + chrono::Duration::days(days);
+        format!(
+            r#"<div class="article-event">
+                <div class="article-event__date-date">{}</div>
+                <div class="article-event__date-time" aria-label="20:00">20.00</div>
+                <div class="activity-ticket__calendar"><a href="{}/{}.ics">{}</a></div>
+            </div>"#,
+            date.format("%d.%m.%y"),
+            url,
+            id,
+            id
+        )
+    };
+    let page = format!("{}{}", row(1, 900001), row(2, 900002));
+    let scraper = Scraper::builder(Schauspielhaus)
+        .language(Language::De)
+        .build_with_fetcher(ReplayFetcher::new("testdata/replay"));
+    let theater_id = get_theaters(connection).unwrap()[0].id;
+
+    let (mut play, failures) = scraper.play_from_page(url, &page).await;
+    assert!(failures.is_empty());
+    assert_eq!(play.screenings.len(), 2);
+    play.play.theater_id = theater_id;
+    let stored = create_play_with_screenings(connection, play, true).unwrap();
+
+    // the first row can't be parsed, its screening is still listed
+    let broken = page.replacen("activity-ticket__calendar", "activity-ticket__broken", 1);
+    let (mut play, failures) = scraper.play_from_page(url, &broken).await;
+    assert_eq!(failures.len(), 1);
+    assert_eq!(play.screenings.len(), 1);
+    play.play.theater_id = theater_id;
+    let updated = create_play_with_screenings(connection, play, failures.is_empty()).unwrap();
+    let removed = screenings::table
+        .filter(screenings::play_id.eq(stored.play.id))
+        .filter(screenings::removed_at.is_not_null())
+        .count()
+        .get_result::<i64>(connection);
+    cleanup(connection);
+    assert_eq!(removed.unwrap(), 0);
+    assert_eq!(updated.screenings, stored.screenings);
+}
+
+#[test]
+fn test_archive_with_partial_calendar() {
+    use diesel::prelude::*;
+    use schauspielhaus::models::create_play_with_screenings;
+    use schauspielhaus::models::update_play_status;
+    use schauspielhaus::schema::{plays, theaters};
+    use std::collections::HashSet;
+
+    let connection = &mut establish_connection();
+    let slug = "test-archive-with-partial-calendar";
+    let cleanup = |connection: &mut PgConnection| {
+        let theater = theaters::table
+            .filter(theaters::slug.eq(slug))
+            .select(theaters::id);
+        diesel::delete(plays::table.filter(plays::theater_id.eq_any(theater)))
+            .execute(connection)
+            .unwrap();
+        diesel::delete(theaters::table.filter(theaters::slug.eq(slug)))
+            .execute(connection)
+            .unwrap();
+    };
+    cleanup(connection);
+
+    let theater = put_theater(
+        connection,
+        NewTheater {
+            slug,
+            name: "Test",
+            base_url: "",
+        },
+    )
+    .unwrap();
+    let now = time::OffsetDateTime::now_utc();
+    // neither play is listed, one has an upcoming screening
+    for (days, url) in [(1, "/upcoming"), (-1, "/past")] {
+        let mut play = PlayWithScreenings::default();
+        play.play.url = url.to_string();
+        play.play.theater_id = theater.id;
+        play.screenings = vec![Screening {
+            id: 0,
+            play_id: 0,
+            webid: format!("{}{}", slug, url),
+            venue_name: None,
+            url: format!("{}{}.ics", slug, url),
+            start_time: now + time::Duration::days(days),
+            ticket_url: "".to_string(),
+            venue_id: None,
+            end_time: None,
+            ticket_status: TicketStatus::default(),
+            presale_date: None,
+            tags: schauspielhaus::models::ScreeningTags(vec![]),
+            removed_at: None,
+        }];
+        create_play_with_screenings(connection, play, true).unwrap();
+    }
+
+    let archived = |connection: &mut PgConnection, calendar_complete| {
+        update_play_status(
+            connection,
+            theater.id,
+            &HashSet::new(),
+            calendar_complete,
+            now,
+        )
+        .unwrap()
+        .archived
+        .into_iter()
+        .map(|p| p.url)
+        .collect::<Vec<String>>()
+    };
+    // a play missing from a partial calendar may be on a page that failed
+    let partial = archived(connection, false);
+    let complete = archived(connection, true);
+    cleanup(connection);
+    assert_eq!(partial, vec!["/past"]);
+    assert_eq!(complete, vec!["/upcoming"]);
+}
+
+#[test]
+fn test_follow_message() {
+    let mut play = PlayWithScreenings::default();
+    play.play.name = "Liebe, einfach ausserirdisch".to_string();
+    play.play.url = "/de/kalender/30546/liebe-einfach-ausserirdisch".to_string();
+    let screening = Screening {
+        id: 1,
+        play_id: 1,
+        webid: "event_31438@www.schauspielhaus.ch".to_string(),
+        venue_name: Some("Pfauen".to_string()),
+        url: "/de/kalender/30546/liebe-einfach-ausserirdisch/31438.ics".to_string(),
+        start_time: time::macros::datetime!(2024-10-02 20:00 +02:00),
+        ticket_url: "".to_string(),
+        venue_id: None,
+        end_time: None,
+        ticket_status: TicketStatus::SoldOut,
+        presale_date: None,
+        tags: schauspielhaus::models::ScreeningTags(vec![ScreeningTag::Surtitles]),
+        removed_at: None,
+    };
+    let update = PlayChanges {
+        play,
+        new_play: false,
+        new_screenings: vec![screening],
+        removed_screenings: vec![],
+        time_changes: vec![],
+        ticket_changes: vec![],
+        description_changed: false,
+    };
+    assert_eq!(
+        follow_message(
+            &update,
+            &["Sophie Rois".to_string()],
+            &schauspielhaus_theater(),
+            Language::En
+        ),
+        "🔔 New screenings with Sophie Rois:\n\
+         [*Liebe, einfach ausserirdisch*](https://www.schauspielhaus.ch/de/kalender/30546/liebe-einfach-ausserirdisch)\n\
+         \\- Wednesday 02\\.10\\.2024 20:00, Pfauen 💬 Surtitles ❌ Sold out"
+    );
+    assert!(follow_message(
+        &update,
+        &["Sophie Rois".to_string()],
+        &schauspielhaus_theater(),
+        Language::De
+    )
+    .starts_with("🔔 Neue Vorstellungen mit Sophie Rois:\n"));
+    assert_eq!(
+        option(&update.new_screenings[0], Language::De),
+        "Mittwoch 02.10.2024 20:00, Pfauen 💬 Übertitel"
+    );
+}
+
+#[cfg(test)]
+fn schauspielhaus_theater() -> Theater {
+    Theater {
+        id: 1,
+        slug: "schauspielhaus".to_string(),
+        name: "Schauspielhaus Zürich".to_string(),
+        base_url: "https://www.schauspielhaus.ch".to_string(),
+    }
+}
+
+#[test]
+fn test_screening_line() {
+    let mut screening = Screening {
+        id: 1,
+        play_id: 1,
+        webid: "event_31438@www.schauspielhaus.ch".to_string(),
+        venue_name: Some("Pfauen".to_string()),
+        url: "/de/kalender/30546/liebe-einfach-ausserirdisch/31438.ics".to_string(),
+        start_time: time::macros::datetime!(2024-10-02 20:00 +02:00),
+        ticket_url: "".to_string(),
+        venue_id: None,
+        end_time: None,
+        ticket_status: TicketStatus::Available,
+        presale_date: None,
+        tags: Default::default(),
+        removed_at: None,
+    };
+    assert_eq!(
+        screening_line(&screening, Language::En),
+        "Wednesday 02\\.10\\.2024 20:00, Pfauen 🎟️ Tickets"
+    );
+    screening.ticket_status = TicketStatus::Cancelled;
+    assert_eq!(
+        screening_line(&screening, Language::En),
+        "~Wednesday 02\\.10\\.2024 20:00, Pfauen~ 🚫 Cancelled"
+    );
+    screening.removed_at = Some(time::macros::datetime!(2024-09-30 12:00 UTC));
+    assert_eq!(
+        screening_line(&screening, Language::De),
+        "~Mittwoch 02\\.10\\.2024 20:00, Pfauen~ 🗑️ Nicht mehr im Spielplan"
+    );
+}
+
+#[test]
+fn test_photo_caption() {
+    let theater = schauspielhaus_theater();
+    let mut play = PlayWithScreenings::default();
+    play.play.name = "Liebe, einfach ausserirdisch".to_string();
+    play.play.url = "/de/kalender/30546/liebe-einfach-ausserirdisch".to_string();
+    assert_eq!(
+        photo_caption(&play, &theater, Language::De),
+        "[*Liebe, einfach ausserirdisch*](https://www.schauspielhaus.ch/de/kalender/30546/liebe-einfach-ausserirdisch)"
+    );
+    play.play.description = "Ein Abend.".to_string();
+    assert!(photo_caption(&play, &theater, Language::De).ends_with(")\n\nEin Abend\\."));
+
+    // long descriptions are shortened to fit the caption
+    play.play.description = "Ein Abend. ".repeat(200);
+    let caption = photo_caption(&play, &theater, Language::De);
+    let description = caption.split("\n\n").nth(1).unwrap().replace('\\', "");
+    assert!(description.ends_with('…'));
+    assert!(play.play.name.chars().count() + 2 + description.chars().count() <= MAX_CAPTION_LENGTH);
+}
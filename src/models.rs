@@ -1,10 +1,17 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 
-use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use chrono::{DateTime, Utc};
 use chrono_tz::{Europe::Zurich, Tz};
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
 use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::{Array, Nullable, Text};
 use serde;
-use time::{format_description, OffsetDateTime};
+use std::io::Write;
+use time::{Date, OffsetDateTime};
 
 #[derive(Queryable, Identifiable, Selectable, Debug, PartialEq, AsChangeset, Insertable, Clone)]
 #[diesel(table_name = crate::schema::chats)]
@@ -13,6 +20,79 @@ use time::{format_description, OffsetDateTime};
 pub struct Chat {
     pub id: i64,
     pub name: String, // just used for logging
+    pub language: Language,
+    // mark the closed topics of archived plays in their name
+    pub hide_archived: bool,
+    // the categories of plays that get a topic
+    pub topic_categories: EventCategories,
+}
+
+// Language of the website and of the messages of the bot in a chat, stored as
+// its code ("de", "en").
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, AsExpression, FromSqlRow, serde::Serialize,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    De,
+    En,
+}
+
+impl Language {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::De => "de",
+            Language::En => "en",
+        }
+    }
+
+    pub fn weekday(&self, weekday: chrono::Weekday) -> &'static str {
+        use chrono::Weekday::*;
+        match (self, weekday) {
+            (Language::De, Mon) => "Montag",
+            (Language::De, Tue) => "Dienstag",
+            (Language::De, Wed) => "Mittwoch",
+            (Language::De, Thu) => "Donnerstag",
+            (Language::De, Fri) => "Freitag",
+            (Language::De, Sat) => "Samstag",
+            (Language::De, Sun) => "Sonntag",
+            (Language::En, Mon) => "Monday",
+            (Language::En, Tue) => "Tuesday",
+            (Language::En, Wed) => "Wednesday",
+            (Language::En, Thu) => "Thursday",
+            (Language::En, Fri) => "Friday",
+            (Language::En, Sat) => "Saturday",
+            (Language::En, Sun) => "Sunday",
+        }
+    }
+}
+
+impl std::str::FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "de" => Ok(Language::De),
+            "en" => Ok(Language::En),
+            _ => Err(format!("unknown language: {}", s)),
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for Language {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.code().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for Language {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let s = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Ok(s.parse()?)
+    }
 }
 
 #[derive(Insertable, AsChangeset, Clone)]
@@ -23,6 +103,28 @@ pub struct NewChat<'a> {
     pub name: &'a str,
 }
 
+// Theater is a theater whose programme is scraped, the urls of its plays are
+// paths on its website.
+#[derive(Default, Queryable, Identifiable, Selectable, Debug, PartialEq, Clone)]
+#[diesel(table_name = crate::schema::theaters)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Theater {
+    pub id: i32,
+    // identifies the theater in commands, e.g. "schauspielhaus"
+    pub slug: String,
+    pub name: String,
+    pub base_url: String,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[diesel(table_name = crate::schema::theaters)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewTheater<'a> {
+    pub slug: &'a str,
+    pub name: &'a str,
+    pub base_url: &'a str,
+}
+
 #[derive(
     Queryable,
     Associations,
@@ -46,6 +148,8 @@ pub struct Topic {
     pub last_updated: OffsetDateTime,
     pub pinned_message_id: i32,
     pub pinned_message_hash: i64,
+    // the topic was closed because its play is archived
+    pub closed: bool,
 }
 
 #[derive(
@@ -60,17 +164,95 @@ pub struct Play {
     pub description: String,
     pub image_url: String,
     pub meta_info: String,
+    pub duration_minutes: Option<i32>,
+    pub intermission: Option<bool>,
+    pub language: Option<String>,
+    pub surtitles: Option<String>,
+    pub age_recommendation: Option<String>,
+    pub content_warnings: Option<String>,
+    // Hash of the play page the play was parsed from.
+    pub content_hash: Option<String>,
+    // Last time the play was listed in the calendar.
+    pub last_seen_at: Option<OffsetDateTime>,
+    // Set while the play is not listed or has no upcoming screenings.
+    pub archived_at: Option<OffsetDateTime>,
+    pub theater_id: i32,
+    // The description with the emphasis, links and paragraphs of the play
+    // page, see telegram::html_to_markdown.
+    pub description_html: String,
+    pub category: EventCategory,
+}
+
+impl Play {
+    // meta_lines returns the meta info of the play as lines of plain text,
+    // starting with the structured fields followed by the remaining meta info.
+    // Only the duration is formatted in `language`, the rest is scraped text.
+    pub fn meta_lines(&self, language: Language) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.duration_minutes.is_some() || self.intermission.is_some() {
+            let (hours_unit, minutes_unit) = match language {
+                Language::De => ("Std.", "Min."),
+                Language::En => ("h", "min"),
+            };
+            let mut duration = Vec::new();
+            if let Some(minutes) = self.duration_minutes {
+                duration.push(match (minutes / 60, minutes % 60) {
+                    (0, m) => format!("{} {}", m, minutes_unit),
+                    (h, 0) => format!("{} {}", h, hours_unit),
+                    (h, m) => format!("{} {} {} {}", h, hours_unit, m, minutes_unit),
+                });
+            }
+            match (self.intermission, language) {
+                (Some(true), Language::De) => duration.push("mit Pause".to_string()),
+                (Some(false), Language::De) => duration.push("keine Pause".to_string()),
+                (Some(true), Language::En) => duration.push("with intermission".to_string()),
+                (Some(false), Language::En) => duration.push("no intermission".to_string()),
+                (None, _) => {}
+            }
+            lines.push(format!("⏱️ {}", duration.join(", ")));
+        }
+        if let Some(language) = &self.language {
+            lines.push(format!("🗣️ {}", language));
+        }
+        if let Some(surtitles) = &self.surtitles {
+            lines.push(format!("💬 {}", surtitles));
+        }
+        if let Some(age) = &self.age_recommendation {
+            lines.push(format!("👤 {}", age));
+        }
+        if let Some(warnings) = &self.content_warnings {
+            lines.push(format!("⚠️ {}", warnings));
+        }
+        lines.extend(
+            self.meta_info
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(|l| l.to_string()),
+        );
+        lines
+    }
 }
 
 #[derive(Insertable, AsChangeset, Clone)]
 #[diesel(table_name = crate::schema::plays)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
 pub struct NewPlay<'a> {
     pub url: &'a str,
     pub name: &'a str,
     pub description: &'a str,
     pub image_url: &'a str,
     pub meta_info: &'a str,
+    pub duration_minutes: Option<i32>,
+    pub intermission: Option<bool>,
+    pub language: Option<&'a str>,
+    pub surtitles: Option<&'a str>,
+    pub age_recommendation: Option<&'a str>,
+    pub content_warnings: Option<&'a str>,
+    pub content_hash: Option<&'a str>,
+    pub theater_id: i32,
+    pub description_html: &'a str,
+    pub category: EventCategory,
 }
 
 #[derive(
@@ -83,50 +265,606 @@ pub struct Screening {
     pub id: i32,
     pub play_id: i32,
     pub webid: String,
-    pub location: String,
+    // Name of the venue of the screening, read from the venues table.
+    #[diesel(select_expression = crate::schema::venues::name.nullable())]
+    #[diesel(select_expression_type = diesel::dsl::Nullable<crate::schema::venues::name>)]
+    pub venue_name: Option<String>,
     pub url: String,
     pub start_time: OffsetDateTime,
     pub ticket_url: String,
+    pub venue_id: Option<i32>,
+    pub end_time: Option<OffsetDateTime>,
+    pub ticket_status: TicketStatus,
+    pub presale_date: Option<Date>,
+    pub tags: ScreeningTags,
+    // Time at which the screening was no longer listed on the play page.
+    pub removed_at: Option<OffsetDateTime>,
+}
+
+// TicketStatus is the ticket availability of a screening as shown on the
+// website. It is stored as text in the ticket_status column of screenings.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, AsExpression, FromSqlRow, serde::Serialize,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketStatus {
+    #[default]
+    Available,
+    FewLeft,
+    SoldOut,
+    Waitlist,
+    // presale has not started yet, the start date is in presale_date if known
+    Presale,
+    Cancelled,
+    FreeEntry,
+}
+
+impl TicketStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TicketStatus::Available => "available",
+            TicketStatus::FewLeft => "few_left",
+            TicketStatus::SoldOut => "sold_out",
+            TicketStatus::Waitlist => "waitlist",
+            TicketStatus::Presale => "presale",
+            TicketStatus::Cancelled => "cancelled",
+            TicketStatus::FreeEntry => "free_entry",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            TicketStatus::Available => "🎟️",
+            TicketStatus::FewLeft => "⏳",
+            TicketStatus::SoldOut => "❌",
+            TicketStatus::Waitlist => "📝",
+            TicketStatus::Presale => "🗓️",
+            TicketStatus::Cancelled => "🚫",
+            TicketStatus::FreeEntry => "🆓",
+        }
+    }
+
+    pub fn label(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (TicketStatus::Available, _) => "Tickets",
+            (TicketStatus::FewLeft, Language::De) => "Wenige Tickets",
+            (TicketStatus::SoldOut, Language::De) => "Ausverkauft",
+            (TicketStatus::Waitlist, Language::De) => "Warteliste",
+            (TicketStatus::Presale, Language::De) => "Vorverkauf",
+            (TicketStatus::Cancelled, Language::De) => "Entfällt",
+            (TicketStatus::FreeEntry, Language::De) => "Eintritt frei",
+            (TicketStatus::FewLeft, Language::En) => "Few tickets left",
+            (TicketStatus::SoldOut, Language::En) => "Sold out",
+            (TicketStatus::Waitlist, Language::En) => "Waiting list",
+            (TicketStatus::Presale, Language::En) => "Presale",
+            (TicketStatus::Cancelled, Language::En) => "Cancelled",
+            (TicketStatus::FreeEntry, Language::En) => "Free entry",
+        }
+    }
+}
+
+impl std::str::FromStr for TicketStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "available" => Ok(TicketStatus::Available),
+            "few_left" => Ok(TicketStatus::FewLeft),
+            "sold_out" => Ok(TicketStatus::SoldOut),
+            "waitlist" => Ok(TicketStatus::Waitlist),
+            "presale" => Ok(TicketStatus::Presale),
+            "cancelled" => Ok(TicketStatus::Cancelled),
+            "free_entry" => Ok(TicketStatus::FreeEntry),
+            _ => Err(format!("unknown ticket status: {}", s)),
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for TicketStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for TicketStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let s = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Ok(s.parse()?)
+    }
+}
+
+// EventCategory is the kind of event of a play, inferred from its calendar
+// item. It is stored as text in the category column of plays.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, AsExpression, FromSqlRow, serde::Serialize,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum EventCategory {
+    // a production of the repertoire
+    #[default]
+    Production,
+    GuestPerformance,
+    // talks, readings and lectures
+    Talk,
+    Concert,
+    // workshops, courses, guided tours and other formats to take part in
+    Workshop,
+    // Junges Schauspielhaus, plays and courses for children and young people
+    Young,
+}
+
+impl EventCategory {
+    pub const ALL: [EventCategory; 6] = [
+        EventCategory::Production,
+        EventCategory::GuestPerformance,
+        EventCategory::Talk,
+        EventCategory::Concert,
+        EventCategory::Workshop,
+        EventCategory::Young,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventCategory::Production => "production",
+            EventCategory::GuestPerformance => "guest_performance",
+            EventCategory::Talk => "talk",
+            EventCategory::Concert => "concert",
+            EventCategory::Workshop => "workshop",
+            EventCategory::Young => "young",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            EventCategory::Production => "🎭",
+            EventCategory::GuestPerformance => "🧳",
+            EventCategory::Talk => "🎙️",
+            EventCategory::Concert => "🎵",
+            EventCategory::Workshop => "🛠️",
+            EventCategory::Young => "🧒",
+        }
+    }
+
+    pub fn label(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (EventCategory::Production, Language::De) => "Inszenierung",
+            (EventCategory::GuestPerformance, Language::De) => "Gastspiel",
+            (EventCategory::Talk, Language::De) => "Gespräch",
+            (EventCategory::Concert, Language::De) => "Konzert",
+            (EventCategory::Workshop, Language::De) => "Workshop",
+            (EventCategory::Young, _) => "Junges Schauspielhaus",
+            (EventCategory::Production, Language::En) => "Production",
+            (EventCategory::GuestPerformance, Language::En) => "Guest performance",
+            (EventCategory::Talk, Language::En) => "Talk",
+            (EventCategory::Concert, Language::En) => "Concert",
+            (EventCategory::Workshop, Language::En) => "Workshop",
+        }
+    }
+}
+
+// EventCategory parses the stored names as well as the labels in all
+// languages, ignoring case, so that users can type either in commands.
+impl std::str::FromStr for EventCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        EventCategory::ALL
+            .into_iter()
+            .find(|category| {
+                category.as_str() == s
+                    || [Language::De, Language::En]
+                        .iter()
+                        .any(|l| category.label(*l).to_lowercase() == s)
+            })
+            .ok_or_else(|| format!("unknown event category: {}", s))
+    }
+}
+
+impl ToSql<Text, Pg> for EventCategory {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for EventCategory {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let s = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Ok(s.parse()?)
+    }
+}
+
+// EventCategories are the categories of plays that get a topic in a chat,
+// stored as a text array in the topic_categories column of chats. All
+// categories by default.
+#[derive(Debug, Clone, PartialEq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Array<Nullable<Text>>)]
+pub struct EventCategories(pub Vec<EventCategory>);
+
+impl Default for EventCategories {
+    fn default() -> Self {
+        EventCategories(EventCategory::ALL.to_vec())
+    }
+}
+
+impl EventCategories {
+    pub fn contains(&self, category: EventCategory) -> bool {
+        self.0.contains(&category)
+    }
+}
+
+impl ToSql<Array<Nullable<Text>>, Pg> for EventCategories {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        let categories = self.0.iter().map(|c| Some(c.as_str())).collect::<Vec<_>>();
+        <Vec<Option<&str>> as ToSql<Array<Nullable<Text>>, Pg>>::to_sql(
+            &categories,
+            &mut out.reborrow(),
+        )
+    }
+}
+
+impl FromSql<Array<Nullable<Text>>, Pg> for EventCategories {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let categories =
+            <Vec<Option<String>> as FromSql<Array<Nullable<Text>>, Pg>>::from_sql(bytes)?;
+        Ok(EventCategories(
+            categories
+                .into_iter()
+                .flatten()
+                .map(|c| c.parse())
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+// ScreeningTag marks a special screening, e.g. a premiere or a screening with
+// surtitles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningTag {
+    Premiere,
+    LastShow,
+    Introduction,
+    Talk,
+    Surtitles,
+    Relaxed,
+}
+
+impl ScreeningTag {
+    pub const ALL: [ScreeningTag; 6] = [
+        ScreeningTag::Premiere,
+        ScreeningTag::LastShow,
+        ScreeningTag::Introduction,
+        ScreeningTag::Talk,
+        ScreeningTag::Surtitles,
+        ScreeningTag::Relaxed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScreeningTag::Premiere => "premiere",
+            ScreeningTag::LastShow => "last_show",
+            ScreeningTag::Introduction => "introduction",
+            ScreeningTag::Talk => "talk",
+            ScreeningTag::Surtitles => "surtitles",
+            ScreeningTag::Relaxed => "relaxed",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            ScreeningTag::Premiere => "🌟",
+            ScreeningTag::LastShow => "👋",
+            ScreeningTag::Introduction => "💡",
+            ScreeningTag::Talk => "🗨️",
+            ScreeningTag::Surtitles => "💬",
+            ScreeningTag::Relaxed => "🫶",
+        }
+    }
+
+    pub fn label(&self, language: Language) -> &'static str {
+        match (self, language) {
+            (ScreeningTag::Premiere, _) => "Premiere",
+            (ScreeningTag::LastShow, Language::De) => "Dernière",
+            (ScreeningTag::Introduction, Language::De) => "Einführung",
+            (ScreeningTag::Talk, Language::De) => "Publikumsgespräch",
+            (ScreeningTag::Surtitles, Language::De) => "Übertitel",
+            (ScreeningTag::Relaxed, Language::De) => "Relaxed Performance",
+            (ScreeningTag::LastShow, Language::En) => "Last show",
+            (ScreeningTag::Introduction, Language::En) => "Introduction",
+            (ScreeningTag::Talk, Language::En) => "Talk",
+            (ScreeningTag::Surtitles, Language::En) => "Surtitles",
+            (ScreeningTag::Relaxed, Language::En) => "Relaxed performance",
+        }
+    }
+}
+
+// ScreeningTag parses the stored names as well as the labels in all languages,
+// ignoring case, so that users can type either in commands.
+impl std::str::FromStr for ScreeningTag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        ScreeningTag::ALL
+            .into_iter()
+            .find(|tag| {
+                tag.as_str() == s
+                    || [Language::De, Language::En]
+                        .iter()
+                        .any(|l| tag.label(*l).to_lowercase() == s)
+            })
+            .ok_or_else(|| format!("unknown screening tag: {}", s))
+    }
+}
+
+// ScreeningTags are the tags of a screening, stored as a text array in the
+// tags column of screenings.
+#[derive(Debug, Default, Clone, PartialEq, AsExpression, FromSqlRow, serde::Serialize)]
+#[diesel(sql_type = Array<Nullable<Text>>)]
+#[serde(transparent)]
+pub struct ScreeningTags(pub Vec<ScreeningTag>);
+
+impl ScreeningTags {
+    pub fn contains(&self, tag: ScreeningTag) -> bool {
+        self.0.contains(&tag)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ScreeningTag> {
+        self.0.iter()
+    }
+}
+
+impl ToSql<Array<Nullable<Text>>, Pg> for ScreeningTags {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        let tags = self.0.iter().map(|t| Some(t.as_str())).collect::<Vec<_>>();
+        <Vec<Option<&str>> as ToSql<Array<Nullable<Text>>, Pg>>::to_sql(&tags, &mut out.reborrow())
+    }
+}
+
+impl FromSql<Array<Nullable<Text>>, Pg> for ScreeningTags {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let tags = <Vec<Option<String>> as FromSql<Array<Nullable<Text>>, Pg>>::from_sql(bytes)?;
+        Ok(ScreeningTags(
+            tags.into_iter()
+                .flatten()
+                .map(|t| t.parse())
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+impl Screening {
+    // takes_place is false for screenings that are cancelled or that were
+    // removed from the website.
+    pub fn takes_place(&self) -> bool {
+        self.removed_at.is_none() && self.ticket_status != TicketStatus::Cancelled
+    }
+
+    // venue returns the name of the venue of the screening, if it is known.
+    pub fn venue(&self) -> Option<&str> {
+        self.venue_name.as_deref()
+    }
+
+    // time_range formats the start and, if known, the end of the screening in
+    // Zurich time, e.g. "Montag 23.09.2024 19:30–21:45" for "%A %d.%m.%Y".
+    pub fn time_range(&self, date_format: &str) -> String {
+        let start = to_zurich_time(self.start_time);
+        let start_str = format!("{} {}", start.format(date_format), start.format("%H:%M"));
+        match self.end_time {
+            Some(end) => format!("{}–{}", start_str, to_zurich_time(end).format("%H:%M")),
+            None => start_str,
+        }
+    }
+}
+
+#[derive(Queryable, Identifiable, Selectable, Debug, PartialEq, Clone, serde::Serialize)]
+#[diesel(table_name = crate::schema::venues)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Venue {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[diesel(table_name = crate::schema::venues)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewVenue<'a> {
+    pub name: &'a str,
 }
 
 pub fn to_zurich_time(offset_datetime: OffsetDateTime) -> DateTime<Tz> {
     let utc_datetime: DateTime<Utc> =
         DateTime::from_timestamp(offset_datetime.unix_timestamp(), 0).unwrap();
-    let zurich_datetime = utc_datetime.with_timezone(&Zurich);
-    zurich_datetime
+    utc_datetime.with_timezone(&Zurich)
 }
 
 // Implement std::fmt::Display for Screening
 impl std::fmt::Display for Screening {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "[{}]({}{})",
-            to_zurich_time(self.start_time)
-                .format("%d.%m.%Y %H:%M")
-                .to_string(),
-            self.url,
-            self.webid
-        )
+        let start = self.time_range("%d.%m.%Y");
+        match self.venue() {
+            Some(venue) => write!(f, "[{}, {}]({}{})", start, venue, self.url, self.webid),
+            None => write!(f, "[{}]({}{})", start, self.url, self.webid),
+        }
     }
 }
 
 #[derive(Insertable, AsChangeset, Clone)]
 #[diesel(table_name = crate::schema::screenings)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
 pub struct NewScreening<'a> {
     pub play_id: i32,
     pub webid: &'a str,
-    pub location: &'a str,
     pub url: &'a str,
     pub start_time: OffsetDateTime,
     pub ticket_url: &'a str,
+    pub venue_id: Option<i32>,
+    pub end_time: Option<OffsetDateTime>,
+    pub ticket_status: TicketStatus,
+    pub presale_date: Option<Date>,
+    pub tags: &'a ScreeningTags,
+    pub removed_at: Option<OffsetDateTime>,
+}
+
+#[derive(Queryable, Identifiable, Selectable, Debug, PartialEq, Clone, serde::Serialize)]
+#[diesel(table_name = crate::schema::persons)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Person {
+    pub id: i32,
+    pub name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[diesel(table_name = crate::schema::persons)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewPerson<'a> {
+    pub name: &'a str,
+    pub url: Option<&'a str>,
+}
+
+#[derive(Insertable, Clone)]
+#[diesel(table_name = crate::schema::play_credits)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewPlayCredit<'a> {
+    pub play_id: i32,
+    pub person_id: i32,
+    pub role: &'a str,
+    pub position: i32,
 }
 
-#[derive(Default, serde::Serialize)]
+#[derive(Queryable, Selectable, Insertable, Debug, PartialEq, Clone)]
+#[diesel(table_name = crate::schema::follows)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Follow {
+    pub chat_id: i64,
+    pub person_id: i32,
+}
+
+diesel::sql_function!(fn lower(x: Text) -> Text);
+
+// Role of the ensemble members, listed after "Mit" on the play page.
+pub const CAST_ROLE: &str = "Mit";
+
+// Roles that are shown as "Regie" in the credits summary.
+const DIRECTION_ROLES: [&str; 2] = ["Regie", "Inszenierung"];
+
+// Number of cast members listed in the credits summary.
+const SUMMARY_CAST_SIZE: usize = 6;
+
+// Credit is a person credited for a role in a play, e.g. "Regie" or
+// CAST_ROLE for the ensemble.
+#[derive(Debug, PartialEq, Clone, serde::Serialize)]
+pub struct Credit {
+    pub role: String,
+    pub name: String,
+    pub url: Option<String>,
+}
+
+// credits_summary returns a compact "Regie: … / Mit: …" line, or None if
+// neither the direction nor the cast is known.
+pub fn credits_summary(credits: &[Credit]) -> Option<String> {
+    let names = |roles: &[&str]| {
+        credits
+            .iter()
+            .filter(|c| roles.contains(&c.role.as_str()))
+            .map(|c| c.name.as_str())
+            .collect::<Vec<&str>>()
+    };
+    let mut parts = Vec::new();
+    let direction = names(&DIRECTION_ROLES);
+    if !direction.is_empty() {
+        parts.push(format!("Regie: {}", direction.join(", ")));
+    }
+    let cast = names(&[CAST_ROLE]);
+    if !cast.is_empty() {
+        let mut cast_str = cast
+            .iter()
+            .take(SUMMARY_CAST_SIZE)
+            .copied()
+            .collect::<Vec<&str>>()
+            .join(", ");
+        if cast.len() > SUMMARY_CAST_SIZE {
+            cast_str.push_str(" u.a.");
+        }
+        parts.push(format!("{}: {}", CAST_ROLE, cast_str));
+    }
+    match parts.is_empty() {
+        true => None,
+        false => Some(parts.join(" / ")),
+    }
+}
+
+#[derive(
+    Queryable,
+    Selectable,
+    Insertable,
+    AsChangeset,
+    Associations,
+    Debug,
+    PartialEq,
+    Clone,
+    serde::Serialize,
+)]
+#[diesel(belongs_to(Play))]
+#[diesel(table_name = crate::schema::play_translations)]
+#[diesel(primary_key(play_id, language))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PlayTranslation {
+    pub play_id: i32,
+    pub language: Language,
+    pub name: String,
+    pub description: String,
+    pub description_html: String,
+}
+
+#[derive(Default, Debug, Clone, serde::Serialize)]
 pub struct PlayWithScreenings {
     pub play: Play,
     pub screenings: Vec<Screening>,
+    pub credits: Vec<Credit>,
+    // the title and description in other languages than German
+    pub translations: Vec<PlayTranslation>,
+}
+
+impl PlayWithScreenings {
+    fn translation(&self, language: Language) -> Option<&PlayTranslation> {
+        self.translations
+            .iter()
+            .find(|t| t.language == language && !t.name.is_empty())
+    }
+
+    // name returns the title of the play in `language`, falling back to German.
+    pub fn name(&self, language: Language) -> &str {
+        match self.translation(language) {
+            Some(t) => &t.name,
+            None => &self.play.name,
+        }
+    }
+
+    // description returns the description of the play in `language`, falling
+    // back to German.
+    pub fn description(&self, language: Language) -> &str {
+        match self.translation(language) {
+            Some(t) => &t.description,
+            None => &self.play.description,
+        }
+    }
+
+    // description_html returns the description HTML of the play in
+    // `language`, falling back to German.
+    pub fn description_html(&self, language: Language) -> &str {
+        match self.translation(language) {
+            Some(t) => &t.description_html,
+            None => &self.play.description_html,
+        }
+    }
 }
 
 pub struct NewPlayWithScreenings<'a> {
@@ -149,7 +887,7 @@ pub fn get_chat_with_topics(
     conn: &mut PgConnection,
     chat_id: i64,
 ) -> Result<ChatWithTopics, diesel::result::Error> {
-    use crate::schema::{chats, plays, screenings, topics};
+    use crate::schema::{chats, plays, screenings, topics, venues};
 
     // Fetch the chat
     let chat = chats::table.find(chat_id).first::<Chat>(conn)?;
@@ -158,11 +896,12 @@ pub fn get_chat_with_topics(
     let results = topics::table
         .inner_join(plays::table.on(plays::id.eq(topics::play_id)))
         .inner_join(screenings::table.on(screenings::play_id.eq(plays::id)))
+        .left_join(venues::table.on(screenings::venue_id.eq(venues::id.nullable())))
         .filter(topics::chat_id.eq(chat_id))
         .select((
             topics::all_columns,
             plays::all_columns,
-            screenings::all_columns,
+            Screening::as_select(),
         ))
         .load::<(Topic, Play, Screening)>(conn)?;
 
@@ -181,14 +920,24 @@ pub fn get_chat_with_topics(
                     PlayWithScreenings {
                         play,
                         screenings: vec![screening],
+                        credits: vec![],
+                        translations: vec![],
                     },
                 )
             });
     }
 
+    let play_ids = topics_map.keys().copied().collect::<Vec<i32>>();
+    let mut credits = get_credits(conn, &play_ids)?;
+    let mut translations = get_translations(conn, &play_ids)?;
+    for (play_id, (_, play_with_screenings)) in topics_map.iter_mut() {
+        play_with_screenings.credits = credits.remove(play_id).unwrap_or_default();
+        play_with_screenings.translations = translations.remove(play_id).unwrap_or_default();
+    }
+
     Ok(ChatWithTopics {
         chat,
-        topics: topics_map.into_iter().map(|(_, v)| v).collect(),
+        topics: topics_map.into_values().collect(),
     })
 }
 
@@ -198,14 +947,25 @@ pub fn get_play(
 ) -> Result<PlayWithScreenings, diesel::result::Error> {
     use crate::schema::plays;
     use crate::schema::screenings;
+    use crate::schema::venues;
 
     let play = plays::table.find(play_id).first::<Play>(conn)?;
 
     let screenings = screenings::table
+        .left_join(venues::table)
         .filter(screenings::play_id.eq(play_id))
-        .load::<Screening>(conn)?;
+        .select(Screening::as_select())
+        .load(conn)?;
 
-    Ok(PlayWithScreenings { play, screenings })
+    let credits = get_credits(conn, &[play_id])?.remove(&play_id);
+    let translations = get_translations(conn, &[play_id])?.remove(&play_id);
+
+    Ok(PlayWithScreenings {
+        play,
+        screenings,
+        credits: credits.unwrap_or_default(),
+        translations: translations.unwrap_or_default(),
+    })
 }
 
 pub fn get_play_for_topic(
@@ -215,17 +975,27 @@ pub fn get_play_for_topic(
     use crate::schema::plays;
     use crate::schema::screenings;
     use crate::schema::topics;
+    use crate::schema::venues;
 
     let topic = topics::table.find(topic_id).first::<Topic>(conn)?;
 
     let play = plays::table.find(topic.play_id).first::<Play>(conn)?;
 
     let screenings = Screening::belonging_to(&play)
+        .left_join(venues::table)
         .select(Screening::as_select())
         .order_by(screenings::start_time.asc())
         .load(conn)?;
 
-    Ok(PlayWithScreenings { play, screenings })
+    let credits = get_credits(conn, &[play.id])?.remove(&play.id);
+    let translations = get_translations(conn, &[play.id])?.remove(&play.id);
+
+    Ok(PlayWithScreenings {
+        play,
+        screenings,
+        credits: credits.unwrap_or_default(),
+        translations: translations.unwrap_or_default(),
+    })
 }
 
 pub fn get_screenings(
@@ -233,18 +1003,60 @@ pub fn get_screenings(
     play_id: i32,
 ) -> Result<Screening, diesel::result::Error> {
     use crate::schema::screenings;
+    use crate::schema::venues;
 
-    screenings::table.find(play_id).first::<Screening>(conn)
+    screenings::table
+        .left_join(venues::table)
+        .filter(screenings::id.eq(play_id))
+        .select(Screening::as_select())
+        .first(conn)
 }
 
+// put_chat creates or renames the chat, keeping the language of known chats.
 pub fn put_chat(conn: &mut PgConnection, chat: Chat) -> Result<Chat, diesel::result::Error> {
     use crate::schema::chats;
-    let changeset_chat = chat.clone();
+    let new_chat = NewChat {
+        id: chat.id,
+        name: &chat.name,
+    };
     diesel::insert_into(chats::table)
-        .values(chat)
+        .values(&chat)
         .on_conflict(chats::id)
         .do_update()
-        .set(&changeset_chat)
+        .set(&new_chat)
+        .get_result::<Chat>(conn)
+}
+
+pub fn set_chat_hide_archived(
+    conn: &mut PgConnection,
+    chat_id: i64,
+    hide_archived: bool,
+) -> Result<Chat, diesel::result::Error> {
+    use crate::schema::chats;
+    diesel::update(chats::table.find(chat_id))
+        .set(chats::hide_archived.eq(hide_archived))
+        .get_result::<Chat>(conn)
+}
+
+pub fn set_chat_topic_categories(
+    conn: &mut PgConnection,
+    chat_id: i64,
+    categories: &EventCategories,
+) -> Result<Chat, diesel::result::Error> {
+    use crate::schema::chats;
+    diesel::update(chats::table.find(chat_id))
+        .set(chats::topic_categories.eq(categories))
+        .get_result::<Chat>(conn)
+}
+
+pub fn set_chat_language(
+    conn: &mut PgConnection,
+    chat_id: i64,
+    language: Language,
+) -> Result<Chat, diesel::result::Error> {
+    use crate::schema::chats;
+    diesel::update(chats::table.find(chat_id))
+        .set(chats::language.eq(language))
         .get_result::<Chat>(conn)
 }
 
@@ -258,6 +1070,402 @@ pub fn get_chats(conn: &mut PgConnection) -> Result<Vec<Chat>, diesel::result::E
     chats::table.load::<Chat>(conn)
 }
 
+// put_theater returns the theater with the slug of `theater`, creating or
+// updating it.
+pub fn put_theater(
+    conn: &mut PgConnection,
+    theater: NewTheater,
+) -> Result<Theater, diesel::result::Error> {
+    use crate::schema::theaters;
+    diesel::insert_into(theaters::table)
+        .values(&theater)
+        .on_conflict(theaters::slug)
+        .do_update()
+        .set(&theater)
+        .get_result::<Theater>(conn)
+}
+
+pub fn get_theater(conn: &mut PgConnection, id: i32) -> Result<Theater, diesel::result::Error> {
+    use crate::schema::theaters;
+    theaters::table.find(id).first::<Theater>(conn)
+}
+
+pub fn get_theaters(conn: &mut PgConnection) -> Result<Vec<Theater>, diesel::result::Error> {
+    use crate::schema::theaters;
+    theaters::table.order(theaters::id).load::<Theater>(conn)
+}
+
+// get_chat_theaters returns the theaters the chat follows, a chat that didn't
+// choose any follows all theaters.
+pub fn get_chat_theaters(
+    conn: &mut PgConnection,
+    chat_id: i64,
+) -> Result<Vec<Theater>, diesel::result::Error> {
+    use crate::schema::{chat_theaters, theaters};
+    let theaters = chat_theaters::table
+        .inner_join(theaters::table)
+        .filter(chat_theaters::chat_id.eq(chat_id))
+        .order(theaters::id)
+        .select(Theater::as_select())
+        .load::<Theater>(conn)?;
+    if theaters.is_empty() {
+        return get_theaters(conn);
+    }
+    Ok(theaters)
+}
+
+// set_chat_theaters replaces the theaters the chat follows.
+pub fn set_chat_theaters(
+    conn: &mut PgConnection,
+    chat_id: i64,
+    theater_ids: &[i32],
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::chat_theaters;
+    conn.transaction(|conn| {
+        diesel::delete(chat_theaters::table.filter(chat_theaters::chat_id.eq(chat_id)))
+            .execute(conn)?;
+        diesel::insert_into(chat_theaters::table)
+            .values(
+                theater_ids
+                    .iter()
+                    .map(|id| {
+                        (
+                            chat_theaters::chat_id.eq(chat_id),
+                            chat_theaters::theater_id.eq(id),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+// put_venue returns the venue with the given name, creating it if necessary.
+pub fn put_venue(conn: &mut PgConnection, name: &str) -> Result<Venue, diesel::result::Error> {
+    use crate::schema::venues;
+    let new_venue = NewVenue { name };
+    diesel::insert_into(venues::table)
+        .values(&new_venue)
+        .on_conflict(venues::name)
+        .do_update()
+        .set(&new_venue)
+        .get_result::<Venue>(conn)
+}
+
+// put_person returns the person with the given name, ignoring case, creating
+// it if necessary. A known person takes the spelling of `name`, so that a
+// name typed in /follow is replaced by the one of the credits. A known url of
+// the person is kept if `url` is None.
+pub fn put_person(
+    conn: &mut PgConnection,
+    name: &str,
+    url: Option<&str>,
+) -> Result<Person, diesel::result::Error> {
+    use crate::schema::persons;
+    let new_person = NewPerson { name, url };
+    match find_person(conn, name)? {
+        Some(person) => diesel::update(&person)
+            .set(&new_person)
+            .get_result::<Person>(conn),
+        None => diesel::insert_into(persons::table)
+            .values(&new_person)
+            .get_result::<Person>(conn),
+    }
+}
+
+// find_person returns the person with the given name, ignoring case.
+pub fn find_person(
+    conn: &mut PgConnection,
+    name: &str,
+) -> Result<Option<Person>, diesel::result::Error> {
+    use crate::schema::persons;
+    persons::table
+        .filter(lower(persons::name).eq(name.to_lowercase()))
+        .first::<Person>(conn)
+        .optional()
+}
+
+// put_follow lets the (private) chat follow the person.
+pub fn put_follow(
+    conn: &mut PgConnection,
+    chat_id: i64,
+    person_id: i32,
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::follows;
+    diesel::insert_into(follows::table)
+        .values(Follow { chat_id, person_id })
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .map(|_| ())
+}
+
+// delete_follow returns whether the chat followed the person.
+pub fn delete_follow(
+    conn: &mut PgConnection,
+    chat_id: i64,
+    person_id: i32,
+) -> Result<bool, diesel::result::Error> {
+    use crate::schema::follows;
+    diesel::delete(
+        follows::table
+            .filter(follows::chat_id.eq(chat_id))
+            .filter(follows::person_id.eq(person_id)),
+    )
+    .execute(conn)
+    .map(|n| n > 0)
+}
+
+pub fn get_followed_persons(
+    conn: &mut PgConnection,
+    chat_id: i64,
+) -> Result<Vec<Person>, diesel::result::Error> {
+    use crate::schema::{follows, persons};
+    follows::table
+        .inner_join(persons::table)
+        .filter(follows::chat_id.eq(chat_id))
+        .order_by(persons::name)
+        .select(Person::as_select())
+        .load(conn)
+}
+
+// get_followers returns the chats that follow someone credited in the play,
+// together with the names of the persons they follow.
+pub fn get_followers(
+    conn: &mut PgConnection,
+    play_id: i32,
+) -> Result<HashMap<i64, Vec<String>>, diesel::result::Error> {
+    use crate::schema::{follows, persons, play_credits};
+
+    let rows = play_credits::table
+        .inner_join(persons::table)
+        .inner_join(follows::table.on(follows::person_id.eq(play_credits::person_id)))
+        .filter(play_credits::play_id.eq(play_id))
+        .order_by(play_credits::position)
+        .select((follows::chat_id, persons::name))
+        .load::<(i64, String)>(conn)?;
+
+    let mut followers: HashMap<i64, Vec<String>> = HashMap::new();
+    for (chat_id, name) in rows {
+        let names = followers.entry(chat_id).or_default();
+        // a person can have several roles in a play
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    Ok(followers)
+}
+
+// get_play_by_url returns the stored play of the theater with the given url,
+// or None if the play is not in the database yet.
+pub fn get_play_by_url(
+    conn: &mut PgConnection,
+    theater_id: i32,
+    url: &str,
+) -> Result<Option<PlayWithScreenings>, diesel::result::Error> {
+    use crate::schema::plays;
+
+    match plays::table
+        .filter(plays::theater_id.eq(theater_id))
+        .filter(plays::url.eq(url))
+        .select(plays::id)
+        .first::<i32>(conn)
+        .optional()?
+    {
+        Some(id) => get_play(conn, id).map(Some),
+        None => Ok(None),
+    }
+}
+
+// get_upcoming_play_urls returns the urls of the plays of the theater that
+// have screenings after `after` that take place, like update_play_status.
+pub fn get_upcoming_play_urls(
+    conn: &mut PgConnection,
+    theater_id: i32,
+    after: OffsetDateTime,
+) -> Result<Vec<String>, diesel::result::Error> {
+    use crate::schema::{plays, screenings};
+
+    plays::table
+        .inner_join(screenings::table)
+        .filter(plays::theater_id.eq(theater_id))
+        .filter(screenings::start_time.gt(after))
+        .filter(screenings::removed_at.is_null())
+        .filter(screenings::ticket_status.ne(TicketStatus::Cancelled))
+        .select(plays::url)
+        .distinct()
+        .order(plays::url)
+        .load::<String>(conn)
+}
+
+// PlayStatusChanges are the plays that were archived or reopened.
+#[derive(Debug, Default)]
+pub struct PlayStatusChanges {
+    pub archived: Vec<Play>,
+    pub reopened: Vec<Play>,
+}
+
+// update_play_status marks the plays of the theater with urls in `listed` as
+// seen at `now`. Plays that are not listed or have no upcoming screenings that
+// take place are archived, archived plays that are listed with upcoming
+// screenings again are reopened. Unless `calendar_complete`, a play that is not
+// listed may be on a calendar page that failed, and is only archived when it
+// has no upcoming screenings.
+pub fn update_play_status(
+    conn: &mut PgConnection,
+    theater_id: i32,
+    listed: &HashSet<String>,
+    calendar_complete: bool,
+    now: OffsetDateTime,
+) -> Result<PlayStatusChanges, diesel::result::Error> {
+    use crate::schema::{plays, screenings};
+
+    conn.transaction(|conn| {
+        let theater_plays = plays::table.filter(plays::theater_id.eq(theater_id));
+        diesel::update(theater_plays.filter(plays::url.eq_any(listed)))
+            .set(plays::last_seen_at.eq(now))
+            .execute(conn)?;
+
+        let upcoming = screenings::table
+            .filter(screenings::start_time.gt(now))
+            .filter(screenings::removed_at.is_null())
+            .filter(screenings::ticket_status.ne(TicketStatus::Cancelled))
+            .select(screenings::play_id)
+            .distinct()
+            .load::<i32>(conn)?
+            .into_iter()
+            .collect::<HashSet<i32>>();
+
+        let mut changes = PlayStatusChanges::default();
+        for play in theater_plays.order(plays::url).load::<Play>(conn)? {
+            let is_listed = listed.contains(&play.url);
+            let has_upcoming = upcoming.contains(&play.id);
+            let archive = !has_upcoming || (!is_listed && calendar_complete);
+            match (play.archived_at, is_listed && has_upcoming) {
+                (None, _) if archive => changes.archived.push(
+                    diesel::update(&play)
+                        .set(plays::archived_at.eq(now))
+                        .get_result::<Play>(conn)?,
+                ),
+                (Some(_), true) => changes.reopened.push(
+                    diesel::update(&play)
+                        .set(plays::archived_at.eq(None::<OffsetDateTime>))
+                        .get_result::<Play>(conn)?,
+                ),
+                _ => {}
+            }
+        }
+        Ok(changes)
+    })
+}
+
+// count_upcoming_screenings returns the number of screenings of the theater
+// after `after` that take place, keyed by the url of their play. Archived plays
+// are left out.
+pub fn count_upcoming_screenings(
+    conn: &mut PgConnection,
+    theater_id: i32,
+    after: OffsetDateTime,
+) -> Result<HashMap<String, i64>, diesel::result::Error> {
+    use crate::schema::{plays, screenings};
+
+    let counts = plays::table
+        .inner_join(screenings::table)
+        .filter(plays::theater_id.eq(theater_id))
+        .filter(plays::archived_at.is_null())
+        .filter(screenings::start_time.gt(after))
+        .filter(screenings::removed_at.is_null())
+        .filter(screenings::ticket_status.ne(TicketStatus::Cancelled))
+        .group_by(plays::url)
+        .select((plays::url, diesel::dsl::count(screenings::id)))
+        .load::<(String, i64)>(conn)?;
+    Ok(counts.into_iter().collect())
+}
+
+// get_image_urls returns the image urls of the plays of the theater that are
+// not archived.
+pub fn get_image_urls(
+    conn: &mut PgConnection,
+    theater_id: i32,
+) -> Result<Vec<String>, diesel::result::Error> {
+    use crate::schema::plays;
+
+    plays::table
+        .filter(plays::theater_id.eq(theater_id))
+        .filter(plays::archived_at.is_null())
+        .filter(plays::image_url.ne(""))
+        .select(plays::image_url)
+        .order(plays::url)
+        .load::<String>(conn)
+}
+
+// get_content_hashes returns the hashes of the play pages the stored plays of
+// the theater were parsed from, keyed by play url.
+pub fn get_content_hashes(
+    conn: &mut PgConnection,
+    theater_id: i32,
+) -> Result<HashMap<String, String>, diesel::result::Error> {
+    use crate::schema::plays;
+
+    let hashes = plays::table
+        .filter(plays::theater_id.eq(theater_id))
+        .filter(plays::content_hash.is_not_null())
+        .select((plays::url, plays::content_hash.assume_not_null()))
+        .load::<(String, String)>(conn)?;
+    Ok(hashes.into_iter().collect())
+}
+
+// get_translations returns the translations of the given plays, keyed by play id.
+pub fn get_translations(
+    conn: &mut PgConnection,
+    play_ids: &[i32],
+) -> Result<HashMap<i32, Vec<PlayTranslation>>, diesel::result::Error> {
+    use crate::schema::play_translations;
+
+    let rows = play_translations::table
+        .filter(play_translations::play_id.eq_any(play_ids))
+        .load::<PlayTranslation>(conn)?;
+
+    let mut translations: HashMap<i32, Vec<PlayTranslation>> = HashMap::new();
+    for translation in rows {
+        translations
+            .entry(translation.play_id)
+            .or_default()
+            .push(translation);
+    }
+    Ok(translations)
+}
+
+// get_credits returns the credits of the given plays in the order of the
+// play pages, keyed by play id.
+pub fn get_credits(
+    conn: &mut PgConnection,
+    play_ids: &[i32],
+) -> Result<HashMap<i32, Vec<Credit>>, diesel::result::Error> {
+    use crate::schema::{persons, play_credits};
+
+    let rows = play_credits::table
+        .inner_join(persons::table)
+        .filter(play_credits::play_id.eq_any(play_ids))
+        .order_by((play_credits::play_id, play_credits::position))
+        .select((
+            play_credits::play_id,
+            play_credits::role,
+            persons::name,
+            persons::url,
+        ))
+        .load::<(i32, String, String, Option<String>)>(conn)?;
+
+    let mut credits: HashMap<i32, Vec<Credit>> = HashMap::new();
+    for (play_id, role, name, url) in rows {
+        credits
+            .entry(play_id)
+            .or_default()
+            .push(Credit { role, name, url });
+    }
+    Ok(credits)
+}
+
 pub fn put_topic(conn: &mut PgConnection, topic: Topic) -> Result<Topic, diesel::result::Error> {
     use crate::schema::topics;
     let changeset_topic = topic.clone();
@@ -277,6 +1485,7 @@ pub fn get_plays_without_topic(
     // Importing necessary methods
     use crate::schema::plays;
     use crate::schema::topics;
+    use crate::schema::venues;
 
     // The subquery to find topics for a given play_id and chat_id
     let subquery = topics::table.filter(
@@ -292,6 +1501,7 @@ pub fn get_plays_without_topic(
 
     // get all screenings for all plays
     let screenings = Screening::belonging_to(&plays)
+        .left_join(venues::table)
         .select(Screening::as_select())
         .load(conn)?;
 
@@ -310,11 +1520,26 @@ pub fn get_plays_and_topics(
     conn: &mut PgConnection,
     chat_id: i64,
 ) -> Result<Vec<PlayAndTopic>, diesel::result::Error> {
-    use crate::schema::{plays, screenings, topics};
+    use crate::schema::{chats, plays, screenings, topics, venues};
+
+    // Only the plays of the theaters the chat follows
+    let theater_ids = get_chat_theaters(conn, chat_id)?
+        .iter()
+        .map(|t| t.id)
+        .collect::<Vec<i32>>();
+    // and of the categories that get a topic in the chat
+    let categories = chats::table
+        .find(chat_id)
+        .select(chats::topic_categories)
+        .first::<EventCategories>(conn)
+        .optional()?
+        .unwrap_or_default();
 
     // First query the plays with their associated topics (if any)
     let results = plays::table
         .left_outer_join(topics::table.on(plays::id.eq(topics::play_id)))
+        .filter(plays::theater_id.eq_any(&theater_ids))
+        .filter(plays::category.eq_any(&categories.0))
         .filter(topics::chat_id.eq(chat_id).or(topics::chat_id.is_null()))
         .select((plays::all_columns, topics::all_columns.nullable()))
         .load::<(Play, Option<Topic>)>(conn)?;
@@ -322,9 +1547,11 @@ pub fn get_plays_and_topics(
     // Fetch screenings for all the plays
     let play_ids = results.iter().map(|(play, _)| play.id).collect::<Vec<_>>();
     let screenings = screenings::table
-        .filter(screenings::play_id.eq_any(play_ids))
+        .left_join(venues::table)
+        .filter(screenings::play_id.eq_any(&play_ids))
         .order_by(screenings::start_time.asc())
-        .load::<Screening>(conn)?;
+        .select(Screening::as_select())
+        .load(conn)?;
 
     // Group the screenings by play_id
     let mut screenings_map: HashMap<i32, Vec<Screening>> = HashMap::new();
@@ -335,25 +1562,43 @@ pub fn get_plays_and_topics(
             .or_insert_with(|| vec![screening.clone()]);
     }
 
-    // Combine the plays, topics, and screenings
+    let mut credits_map = get_credits(conn, &play_ids)?;
+    let mut translations_map = get_translations(conn, &play_ids)?;
+
+    // Combine the plays, topics, screenings, credits and translations
     Ok(results
         .into_iter()
         .map(|(play, topic)| {
             let screenings = screenings_map.get(&play.id).unwrap_or(&vec![]).clone();
+            let credits = credits_map.remove(&play.id).unwrap_or_default();
+            let translations = translations_map.remove(&play.id).unwrap_or_default();
             PlayAndTopic {
-                play: PlayWithScreenings { play, screenings },
+                play: PlayWithScreenings {
+                    play,
+                    screenings,
+                    credits,
+                    translations,
+                },
                 topic,
             }
         })
         .collect())
 }
 
+// create_play_with_screenings stores the play with its screenings, credits and
+// translations. Unless `screenings_complete`, i.e. some screenings of the play
+// page failed to scrape, the stored screenings that are not in the play are
+// kept as they are.
 pub fn create_play_with_screenings(
     conn: &mut PgConnection,
     play: PlayWithScreenings,
+    screenings_complete: bool,
 ) -> Result<PlayWithScreenings, diesel::result::Error> {
+    use crate::schema::play_credits;
+    use crate::schema::play_translations;
     use crate::schema::plays;
     use crate::schema::screenings;
+    use crate::schema::venues;
 
     let new_play: NewPlay = NewPlay {
         url: &play.play.url,
@@ -361,28 +1606,51 @@ pub fn create_play_with_screenings(
         description: &play.play.description,
         image_url: &play.play.image_url,
         meta_info: &play.play.meta_info,
+        duration_minutes: play.play.duration_minutes,
+        intermission: play.play.intermission,
+        language: play.play.language.as_deref(),
+        surtitles: play.play.surtitles.as_deref(),
+        age_recommendation: play.play.age_recommendation.as_deref(),
+        content_warnings: play.play.content_warnings.as_deref(),
+        content_hash: play.play.content_hash.as_deref(),
+        theater_id: play.play.theater_id,
+        description_html: &play.play.description_html,
+        category: play.play.category,
     };
 
     let changeset_play = new_play.clone();
 
-    conn.build_transaction().read_write().run(|conn| {
+    conn.transaction(|conn| {
         let new_play = diesel::insert_into(plays::table)
             .values(new_play)
-            .on_conflict(plays::url)
+            .on_conflict((plays::theater_id, plays::url))
             .do_update()
             .set(&changeset_play)
             .get_result::<Play>(conn)?;
 
-        let screenings = play
+        let mut venue_ids: HashMap<&str, i32> = HashMap::new();
+        for venue in play.screenings.iter().filter_map(|s| s.venue()) {
+            if !venue_ids.contains_key(venue) {
+                venue_ids.insert(venue, put_venue(conn, venue)?.id);
+            }
+        }
+
+        let mut webids = play
             .screenings
             .iter()
             .map(|s| NewScreening {
                 play_id: new_play.id,
                 webid: &s.webid,
-                location: &s.location,
                 url: &s.url,
                 start_time: s.start_time,
                 ticket_url: &s.ticket_url,
+                venue_id: s.venue().and_then(|v| venue_ids.get(v).copied()),
+                end_time: s.end_time,
+                ticket_status: s.ticket_status,
+                presale_date: s.presale_date,
+                tags: &s.tags,
+                // a removed screening that is listed again is back
+                removed_at: None,
             })
             .map(|s| {
                 let changeset_screening = s.clone();
@@ -391,13 +1659,71 @@ pub fn create_play_with_screenings(
                     .on_conflict(screenings::webid)
                     .do_update()
                     .set(&changeset_screening)
-                    .get_result::<Screening>(conn)
+                    .returning(screenings::webid)
+                    .get_result::<String>(conn)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // upcoming screenings that are no longer listed on the play page are
+        // kept and marked as removed, unless they may have failed to scrape
+        let now = OffsetDateTime::now_utc();
+        let vanished = screenings::table
+            .filter(screenings::play_id.eq(new_play.id))
+            .filter(screenings::webid.ne_all(&webids))
+            .filter(screenings::start_time.gt(now))
+            .filter(screenings::removed_at.is_null());
+        if screenings_complete {
+            diesel::update(vanished)
+                .set(screenings::removed_at.eq(now))
+                .execute(conn)?;
+        } else {
+            webids.extend(vanished.select(screenings::webid).load::<String>(conn)?);
+        }
+
+        let screenings = screenings::table
+            .left_join(venues::table)
+            .filter(screenings::webid.eq_any(&webids))
+            .order_by((screenings::start_time.asc(), screenings::webid.asc()))
+            .select(Screening::as_select())
+            .load::<Screening>(conn)?;
+
+        // the credits of a play are replaced as a whole
+        diesel::delete(play_credits::table.filter(play_credits::play_id.eq(new_play.id)))
+            .execute(conn)?;
+        for (position, credit) in play.credits.iter().enumerate() {
+            let person = put_person(conn, &credit.name, credit.url.as_deref())?;
+            diesel::insert_into(play_credits::table)
+                .values(NewPlayCredit {
+                    play_id: new_play.id,
+                    person_id: person.id,
+                    role: &credit.role,
+                    position: position as i32,
+                })
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+        }
+
+        let translations = play
+            .translations
+            .iter()
+            .map(|t| {
+                let translation = PlayTranslation {
+                    play_id: new_play.id,
+                    ..t.clone()
+                };
+                diesel::insert_into(play_translations::table)
+                    .values(&translation)
+                    .on_conflict((play_translations::play_id, play_translations::language))
+                    .do_update()
+                    .set(&translation)
+                    .get_result::<PlayTranslation>(conn)
             })
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(PlayWithScreenings {
             play: new_play,
             screenings,
+            credits: play.credits.clone(),
+            translations,
         })
     })
 }
@@ -0,0 +1,718 @@
+use crate::models::count_upcoming_screenings;
+use crate::models::create_play_with_screenings;
+use crate::models::get_play_by_url;
+use crate::models::get_upcoming_play_urls;
+use crate::models::to_zurich_time;
+use crate::models::update_play_status;
+use crate::models::Play;
+use crate::models::PlayWithScreenings;
+use crate::models::Screening;
+use crate::models::Theater;
+use crate::models::TicketStatus;
+use crate::scrape::failure_summary;
+use crate::scrape::ScrapeError;
+use crate::scrape::ScrapedPlays;
+use diesel::pg::PgConnection;
+use diesel::Connection;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use time::OffsetDateTime;
+
+// Change is a screening of which a value changed, with the value before.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change<T> {
+    pub screening: Screening,
+    pub before: T,
+}
+
+// PlayChanges are the changes of one play compared to the database.
+#[derive(Debug, Clone)]
+pub struct PlayChanges {
+    // The play as it is stored after the update.
+    pub play: PlayWithScreenings,
+    // The play was not in the database, all its screenings are new.
+    pub new_play: bool,
+    pub new_screenings: Vec<Screening>,
+    // Upcoming screenings that are no longer listed on the play page.
+    pub removed_screenings: Vec<Screening>,
+    pub time_changes: Vec<Change<OffsetDateTime>>,
+    pub ticket_changes: Vec<Change<TicketStatus>>,
+    pub description_changed: bool,
+}
+
+impl PlayChanges {
+    pub fn is_empty(&self) -> bool {
+        !self.new_play
+            && self.new_screenings.is_empty()
+            && self.removed_screenings.is_empty()
+            && self.time_changes.is_empty()
+            && self.ticket_changes.is_empty()
+            && !self.description_changed
+    }
+}
+
+// diff_play compares the stored play `before` with the play after the update.
+// Screenings are matched by webid, only removed screenings after `now` count.
+// Screenings that were removed before are left out, they are new when they
+// are listed again.
+pub fn diff_play(
+    before: Option<&PlayWithScreenings>,
+    after: PlayWithScreenings,
+    now: OffsetDateTime,
+) -> PlayChanges {
+    let mut changes = PlayChanges {
+        new_play: before.is_none(),
+        new_screenings: vec![],
+        removed_screenings: vec![],
+        time_changes: vec![],
+        ticket_changes: vec![],
+        description_changed: before.is_some_and(|b| b.play.description != after.play.description),
+        play: after,
+    };
+    let old_screenings = before
+        .map(|b| {
+            b.screenings
+                .iter()
+                .filter(|s| s.removed_at.is_none())
+                .map(|s| (s.webid.as_str(), s))
+                .collect::<HashMap<&str, &Screening>>()
+        })
+        .unwrap_or_default();
+    for screening in &changes.play.screenings {
+        let old = match old_screenings.get(screening.webid.as_str()) {
+            Some(old) => old,
+            None => {
+                changes.new_screenings.push(screening.clone());
+                continue;
+            }
+        };
+        if old.start_time != screening.start_time {
+            changes.time_changes.push(Change {
+                screening: screening.clone(),
+                before: old.start_time,
+            });
+        }
+        if old.ticket_status != screening.ticket_status {
+            changes.ticket_changes.push(Change {
+                screening: screening.clone(),
+                before: old.ticket_status,
+            });
+        }
+    }
+    let webids = changes
+        .play
+        .screenings
+        .iter()
+        .map(|s| s.webid.as_str())
+        .collect::<HashSet<&str>>();
+    if let Some(before) = before {
+        changes.removed_screenings = before
+            .screenings
+            .iter()
+            .filter(|s| s.removed_at.is_none() && s.start_time > now)
+            .filter(|s| !webids.contains(s.webid.as_str()))
+            .cloned()
+            .collect();
+    }
+    changes
+}
+
+// MIN_SCREENINGS_FOR_DROP is the number of stored upcoming screenings below
+// which a drop is not suspicious, e.g. in the summer break.
+const MIN_SCREENINGS_FOR_DROP: i64 = 20;
+
+// MIN_ROWS_FOR_ICS_FALLBACK is the number of parsed screening rows below which
+// rows read from their ICS file are not suspicious.
+const MIN_ROWS_FOR_ICS_FALLBACK: usize = 10;
+
+// MAX_FAILED_PLAYS_PERCENT is the share of the plays in the calendar that may
+// fail before a run needs attention, a single odd play page doesn't.
+const MAX_FAILED_PLAYS_PERCENT: usize = 20;
+
+// Anomaly is a scrape result that looks like the website changed in a way the
+// scraper doesn't understand. Nothing is written to the database when there is
+// one that blocks_storage.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Anomaly {
+    // The calendar could not be loaded, e.g. because its selectors no longer
+    // match.
+    NoCalendar(String),
+    // No play was scraped, the calendar pages list none or all play pages
+    // failed.
+    NoPlays,
+    // Less than half of the upcoming screenings are left.
+    ScreeningDrop { before: i64, after: i64 },
+    // Most screening rows were read from their ICS file, e.g. because the
+    // selectors of their date no longer match.
+    IcsFallback { rows: usize, fallbacks: usize },
+}
+
+impl Anomaly {
+    // blocks_storage tells whether the scraped plays are not stored. Screenings
+    // read from their ICS file are complete, they are just slow to scrape.
+    pub fn blocks_storage(&self) -> bool {
+        !matches!(self, Anomaly::IcsFallback { .. })
+    }
+}
+
+impl std::fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Anomaly::NoCalendar(e) => write!(f, "the calendar could not be loaded: {}", e),
+            Anomaly::NoPlays => write!(f, "no play could be scraped from the calendar"),
+            Anomaly::ScreeningDrop { before, after } => write!(
+                f,
+                "the upcoming screenings dropped from {} to {}",
+                before, after
+            ),
+            Anomaly::IcsFallback { rows, fallbacks } => write!(
+                f,
+                "{} of {} screening rows were read from their ICS file",
+                fallbacks, rows
+            ),
+        }
+    }
+}
+
+// check_scrape compares the scraped plays with the database. `stored` are the
+// upcoming screening counts per play url, see count_upcoming_screenings.
+pub fn check_scrape(
+    scraped: &ScrapedPlays,
+    stored: &HashMap<String, i64>,
+    now: OffsetDateTime,
+) -> Vec<Anomaly> {
+    let mut anomalies = vec![];
+    // every play page failing looks the same as an empty calendar
+    if scraped.plays.is_empty() && scraped.unchanged.is_empty() {
+        anomalies.push(Anomaly::NoPlays);
+    }
+
+    // plays that were not parsed keep their stored screenings
+    let kept = scraped
+        .unchanged
+        .iter()
+        .map(String::as_str)
+        .chain(scraped.failures.iter().map(|e| e.path()))
+        .collect::<HashSet<&str>>();
+    let before = stored.values().sum::<i64>();
+    let after = scraped
+        .plays
+        .values()
+        .flat_map(|p| &p.screenings)
+        .filter(|s| s.start_time > now && s.takes_place())
+        .count() as i64
+        + kept.iter().filter_map(|url| stored.get(*url)).sum::<i64>();
+    if before >= MIN_SCREENINGS_FOR_DROP && after * 2 < before {
+        anomalies.push(Anomaly::ScreeningDrop { before, after });
+    }
+
+    let (rows, fallbacks) = (scraped.screening_rows, scraped.ics_fallbacks);
+    if rows >= MIN_ROWS_FOR_ICS_FALLBACK && fallbacks * 2 > rows {
+        anomalies.push(Anomaly::IcsFallback { rows, fallbacks });
+    }
+    anomalies
+}
+
+// ScrapeReport is what a scrape run of a theater changed in the database.
+#[derive(Debug, Default)]
+pub struct ScrapeReport {
+    pub theater: Theater,
+    // Plays with changes, in the order of their urls.
+    pub plays: Vec<PlayChanges>,
+    // Plays with upcoming screenings that are no longer in the calendar.
+    pub removed_plays: Vec<PlayWithScreenings>,
+    // Plays that were archived because they are not listed anymore or have no
+    // upcoming screenings.
+    pub archived_plays: Vec<Play>,
+    // Archived plays that are listed with upcoming screenings again.
+    pub reopened_plays: Vec<Play>,
+    // Number of plays whose page didn't change.
+    pub unchanged: usize,
+    // Number of plays in the calendar, and of those whose page failed to load
+    // or parse.
+    pub listed: usize,
+    pub failed: usize,
+    pub failures: Vec<ScrapeError>,
+    // What looks like the website changed, see Anomaly::blocks_storage.
+    pub anomalies: Vec<Anomaly>,
+}
+
+impl ScrapeReport {
+    pub fn new_plays(&self) -> impl Iterator<Item = &PlayChanges> {
+        self.plays.iter().filter(|p| p.new_play)
+    }
+
+    // needs_attention tells whether the scraper may be broken and an admin
+    // should look at the report, because of anomalies or because more than
+    // MAX_FAILED_PLAYS_PERCENT of the plays failed.
+    pub fn needs_attention(&self) -> bool {
+        !self.anomalies.is_empty() || self.failed * 100 > self.listed * MAX_FAILED_PLAYS_PERCENT
+    }
+
+    // stored tells whether the scraped plays were written to the database.
+    pub fn stored(&self) -> bool {
+        !self.anomalies.iter().any(Anomaly::blocks_storage)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anomalies.is_empty()
+            && self.plays.is_empty()
+            && self.removed_plays.is_empty()
+            && self.archived_plays.is_empty()
+            && self.reopened_plays.is_empty()
+    }
+}
+
+// sync_plays stores the scraped plays of the theater and reports what changed.
+// Plays that are not listed in the calendar anymore stay in the database and
+// are archived, unless some calendar pages failed. Nothing is stored when
+// check_scrape finds anomalies.
+pub fn sync_plays(
+    conn: &mut PgConnection,
+    theater: &Theater,
+    scraped: ScrapedPlays,
+) -> Result<ScrapeReport, diesel::result::Error> {
+    let now = OffsetDateTime::now_utc();
+    let mut report = ScrapeReport {
+        theater: theater.clone(),
+        unchanged: scraped.unchanged.len(),
+        listed: scraped.listed,
+        failed: scraped
+            .listed
+            .saturating_sub(scraped.plays.len() + scraped.unchanged.len()),
+        ..ScrapeReport::default()
+    };
+
+    let anomalies = check_scrape(
+        &scraped,
+        &count_upcoming_screenings(conn, theater.id, now)?,
+        now,
+    );
+    report.anomalies = anomalies;
+    if !report.stored() {
+        report.failures = scraped.failures;
+        return Ok(report);
+    }
+
+    // a play whose page failed to load is not removed
+    let listed = scraped
+        .plays
+        .keys()
+        .chain(scraped.unchanged.iter())
+        .map(String::as_str)
+        .chain(scraped.failures.iter().map(|e| e.path()))
+        .map(str::to_string)
+        .collect::<HashSet<String>>();
+    // the plays of a run are stored together or not at all
+    conn.transaction(|conn| {
+        // a play that is missing from a partial calendar may still be listed
+        let upcoming = if scraped.partial_calendar {
+            vec![]
+        } else {
+            get_upcoming_play_urls(conn, theater.id, now)?
+        };
+        for url in upcoming {
+            if !listed.contains(&url) {
+                // archived plays were reported when they were archived
+                if let Some(play) = get_play_by_url(conn, theater.id, &url)?
+                    .filter(|p| p.play.archived_at.is_none())
+                {
+                    report.removed_plays.push(play);
+                }
+            }
+        }
+
+        for (url, mut play) in scraped.plays {
+            play.play.theater_id = theater.id;
+            let before = get_play_by_url(conn, theater.id, &url)?;
+            // a screening that failed to scrape may still be listed
+            let screenings_complete = !scraped.incomplete.contains(&url);
+            let after = create_play_with_screenings(conn, play, screenings_complete)?;
+            let changes = diff_play(before.as_ref(), after, now);
+            if !changes.is_empty() {
+                report.plays.push(changes);
+            }
+        }
+
+        let status = update_play_status(conn, theater.id, &listed, !scraped.partial_calendar, now)?;
+        report.archived_plays = status.archived;
+        report.reopened_plays = status.reopened;
+        Ok::<(), diesel::result::Error>(())
+    })?;
+    report.failures = scraped.failures;
+    Ok(report)
+}
+
+// format_time formats a screening time in Zurich time.
+fn format_time(time: OffsetDateTime) -> String {
+    to_zurich_time(time).format("%d.%m.%Y %H:%M").to_string()
+}
+
+impl std::fmt::Display for ScrapeReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let count = |n: usize, what: &str| format!("{} {}", n, what);
+        let sum = |field: fn(&PlayChanges) -> usize| self.plays.iter().map(field).sum::<usize>();
+        writeln!(
+            f,
+            "{}, {}, {}, {}, {}, {}, {}, {}",
+            count(self.new_plays().count(), "new plays"),
+            count(self.removed_plays.len(), "removed plays"),
+            count(sum(|p| p.new_screenings.len()), "new screenings"),
+            count(sum(|p| p.removed_screenings.len()), "removed screenings"),
+            count(sum(|p| p.time_changes.len()), "time changes"),
+            count(sum(|p| p.ticket_changes.len()), "ticket changes"),
+            count(
+                sum(|p| p.description_changed as usize),
+                "description changes"
+            ),
+            count(self.unchanged, "unchanged plays"),
+        )?;
+        if !self.anomalies.is_empty() {
+            if self.stored() {
+                writeln!(f, "The website may have changed:")?;
+            } else {
+                writeln!(f, "Nothing was stored, the website may have changed:")?;
+            }
+            for anomaly in &self.anomalies {
+                writeln!(f, "  ! {}", anomaly)?;
+            }
+        }
+        for play in &self.plays {
+            let status = if play.new_play { "new" } else { "changed" };
+            writeln!(
+                f,
+                "{} ({}): {}",
+                play.play.play.name, status, play.play.play.url
+            )?;
+            if play.description_changed {
+                writeln!(f, "  ~ description")?;
+            }
+            for s in &play.new_screenings {
+                writeln!(f, "  + {}", s.time_range("%d.%m.%Y"))?;
+            }
+            for s in &play.removed_screenings {
+                writeln!(f, "  - {}", s.time_range("%d.%m.%Y"))?;
+            }
+            for c in &play.time_changes {
+                writeln!(
+                    f,
+                    "  ~ {} -> {}",
+                    format_time(c.before),
+                    format_time(c.screening.start_time)
+                )?;
+            }
+            for c in &play.ticket_changes {
+                writeln!(
+                    f,
+                    "  ~ {} tickets {} -> {}",
+                    format_time(c.screening.start_time),
+                    c.before.as_str(),
+                    c.screening.ticket_status.as_str()
+                )?;
+            }
+        }
+        for play in &self.removed_plays {
+            writeln!(f, "{} (removed): {}", play.play.name, play.play.url)?;
+        }
+        for play in &self.archived_plays {
+            writeln!(f, "{} (archived): {}", play.name, play.url)?;
+        }
+        for play in &self.reopened_plays {
+            writeln!(f, "{} (reopened): {}", play.name, play.url)?;
+        }
+        if !self.failures.is_empty() {
+            writeln!(f, "{}", failure_summary(&self.failures))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn test_screening(
+    webid: &str,
+    start_time: OffsetDateTime,
+    ticket_status: TicketStatus,
+) -> Screening {
+    Screening {
+        id: 0,
+        play_id: 1,
+        webid: webid.to_string(),
+        venue_name: Some("Pfauen".to_string()),
+        url: String::new(),
+        start_time,
+        ticket_url: String::new(),
+        venue_id: None,
+        end_time: None,
+        ticket_status,
+        presale_date: None,
+        tags: Default::default(),
+        removed_at: None,
+    }
+}
+
+#[test]
+fn test_diff_play() {
+    use time::macros::datetime;
+
+    let mut before = PlayWithScreenings::default();
+    before.play.name = "Liebe, einfach ausserirdisch".to_string();
+    before.play.url = "/de/kalender/30546/liebe-einfach-ausserirdisch".to_string();
+    before.play.description = "Ein Abend".to_string();
+    before.screenings = vec![
+        // already played, it is not removed when it disappears from the page
+        test_screening(
+            "a",
+            datetime!(2024-09-23 20:00 +02:00),
+            TicketStatus::Available,
+        ),
+        test_screening(
+            "b",
+            datetime!(2024-10-01 20:00 +02:00),
+            TicketStatus::Available,
+        ),
+        test_screening(
+            "c",
+            datetime!(2024-10-02 20:00 +02:00),
+            TicketStatus::Available,
+        ),
+        test_screening(
+            "d",
+            datetime!(2024-10-03 20:00 +02:00),
+            TicketStatus::Available,
+        ),
+    ];
+    let mut after = before.clone();
+    after.play.description = "Ein anderer Abend".to_string();
+    after.screenings = vec![
+        test_screening(
+            "b",
+            datetime!(2024-10-01 19:30 +02:00),
+            TicketStatus::Available,
+        ),
+        test_screening(
+            "c",
+            datetime!(2024-10-02 20:00 +02:00),
+            TicketStatus::SoldOut,
+        ),
+        test_screening(
+            "e",
+            datetime!(2024-10-04 20:00 +02:00),
+            TicketStatus::Available,
+        ),
+    ];
+
+    let changes = diff_play(
+        Some(&before),
+        after.clone(),
+        datetime!(2024-09-30 12:00 UTC),
+    );
+    assert!(!changes.new_play);
+    assert!(changes.description_changed);
+    assert_eq!(changes.new_screenings, vec![after.screenings[2].clone()]);
+    assert_eq!(
+        changes.removed_screenings,
+        vec![before.screenings[3].clone()]
+    );
+    assert_eq!(
+        changes.time_changes,
+        vec![Change {
+            screening: after.screenings[0].clone(),
+            before: datetime!(2024-10-01 20:00 +02:00),
+        }]
+    );
+    assert_eq!(
+        changes.ticket_changes,
+        vec![Change {
+            screening: after.screenings[1].clone(),
+            before: TicketStatus::Available,
+        }]
+    );
+
+    let report = ScrapeReport {
+        plays: vec![changes],
+        unchanged: 2,
+        ..ScrapeReport::default()
+    };
+    assert_eq!(
+        report.to_string(),
+        "0 new plays, 0 removed plays, 1 new screenings, 1 removed screenings, 1 time changes, 1 ticket changes, 1 description changes, 2 unchanged plays
+Liebe, einfach ausserirdisch (changed): /de/kalender/30546/liebe-einfach-ausserirdisch
+  ~ description
+  + 04.10.2024 20:00
+  - 03.10.2024 20:00
+  ~ 01.10.2024 20:00 -> 01.10.2024 19:30
+  ~ 02.10.2024 20:00 tickets available -> sold_out
+"
+    );
+
+    let changes = diff_play(None, after.clone(), datetime!(2024-09-30 12:00 UTC));
+    assert!(changes.new_play);
+    assert!(!changes.description_changed);
+    assert_eq!(changes.new_screenings, after.screenings);
+
+    let changes = diff_play(Some(&after), after.clone(), datetime!(2024-09-30 12:00 UTC));
+    assert!(changes.is_empty());
+
+    // a removed screening is reported once, and as new when it is listed again
+    let mut removed = after.clone();
+    removed.screenings[2].removed_at = Some(datetime!(2024-09-29 12:00 UTC));
+    let mut without = after.clone();
+    without.screenings.pop();
+    let changes = diff_play(Some(&removed), without, datetime!(2024-09-30 12:00 UTC));
+    assert!(changes.is_empty());
+    let changes = diff_play(
+        Some(&removed),
+        after.clone(),
+        datetime!(2024-09-30 12:00 UTC),
+    );
+    assert_eq!(changes.new_screenings, vec![after.screenings[2].clone()]);
+}
+
+#[test]
+fn test_report_archived_plays() {
+    let play = |name: &str, url: &str| Play {
+        name: name.to_string(),
+        url: url.to_string(),
+        ..Play::default()
+    };
+    let report = ScrapeReport {
+        archived_plays: vec![play("Kirschgarten", "/de/kalender/1/der-kirschgarten")],
+        reopened_plays: vec![play("Hamlet", "/de/kalender/2/hamlet")],
+        ..ScrapeReport::default()
+    };
+    assert!(!report.is_empty());
+    assert_eq!(
+        report.to_string(),
+        "0 new plays, 0 removed plays, 0 new screenings, 0 removed screenings, 0 time changes, 0 ticket changes, 0 description changes, 0 unchanged plays
+Kirschgarten (archived): /de/kalender/1/der-kirschgarten
+Hamlet (reopened): /de/kalender/2/hamlet
+"
+    );
+}
+
+#[test]
+fn test_check_scrape() {
+    use time::macros::datetime;
+
+    let now = datetime!(2024-09-30 12:00 UTC);
+    let stored = HashMap::from([
+        ("/de/kalender/1/a".to_string(), 20),
+        ("/de/kalender/2/b".to_string(), 10),
+    ]);
+    assert_eq!(
+        check_scrape(&ScrapedPlays::default(), &stored, now),
+        vec![
+            Anomaly::NoPlays,
+            Anomaly::ScreeningDrop {
+                before: 30,
+                after: 0
+            }
+        ]
+    );
+
+    // a redesign that breaks every play page
+    let mut scraped = ScrapedPlays::default();
+    for url in stored.keys() {
+        scraped.failures.push(ScrapeError::Parse {
+            path: url.to_string(),
+            message: "play has no title".to_string(),
+        });
+    }
+    assert_eq!(check_scrape(&scraped, &stored, now), vec![Anomaly::NoPlays]);
+
+    // the screenings of unchanged and failed plays are kept
+    let mut scraped = ScrapedPlays::default();
+    scraped.unchanged.insert("/de/kalender/1/a".to_string());
+    scraped.failures.push(ScrapeError::Status {
+        path: "/de/kalender/2/b".to_string(),
+        status: 500,
+    });
+    assert_eq!(check_scrape(&scraped, &stored, now), vec![]);
+
+    // a play that lost most of its screenings
+    let play = PlayWithScreenings {
+        screenings: vec![
+            test_screening(
+                "a",
+                datetime!(2024-10-01 20:00 +02:00),
+                TicketStatus::Available,
+            ),
+            // already played
+            test_screening(
+                "b",
+                datetime!(2024-09-01 20:00 +02:00),
+                TicketStatus::Available,
+            ),
+        ],
+        ..PlayWithScreenings::default()
+    };
+    let mut scraped = ScrapedPlays::default();
+    scraped.plays.insert("/de/kalender/1/a".to_string(), play);
+    scraped.unchanged.insert("/de/kalender/2/b".to_string());
+    assert_eq!(
+        check_scrape(&scraped, &stored, now),
+        vec![Anomaly::ScreeningDrop {
+            before: 30,
+            after: 11
+        }]
+    );
+
+    // few screenings are not compared, e.g. in the summer break
+    let stored = HashMap::from([("/de/kalender/1/a".to_string(), 4)]);
+    assert_eq!(check_scrape(&scraped, &stored, now), vec![]);
+
+    // the screenings are still stored when most rows need their ICS file
+    let ics_fallbacks = |fallbacks| ScrapedPlays {
+        unchanged: std::collections::BTreeSet::from(["/de/kalender/1/a".to_string()]),
+        screening_rows: 12,
+        ics_fallbacks: fallbacks,
+        ..ScrapedPlays::default()
+    };
+    assert_eq!(check_scrape(&ics_fallbacks(6), &stored, now), vec![]);
+    let anomalies = check_scrape(&ics_fallbacks(7), &stored, now);
+    assert_eq!(
+        anomalies,
+        vec![Anomaly::IcsFallback {
+            rows: 12,
+            fallbacks: 7
+        }]
+    );
+    let report = ScrapeReport {
+        anomalies,
+        ..ScrapeReport::default()
+    };
+    assert!(report.stored());
+    assert!(report.needs_attention());
+
+    let report = ScrapeReport {
+        anomalies: vec![Anomaly::NoPlays],
+        ..ScrapeReport::default()
+    };
+    assert!(report.needs_attention());
+    assert_eq!(
+        report.to_string(),
+        "0 new plays, 0 removed plays, 0 new screenings, 0 removed screenings, 0 time changes, 0 ticket changes, 0 description changes, 0 unchanged plays
+Nothing was stored, the website may have changed:
+  ! no play could be scraped from the calendar
+"
+    );
+}
+
+#[test]
+fn test_needs_attention() {
+    let report = |failed| ScrapeReport {
+        listed: 10,
+        failed,
+        failures: (0..failed)
+            .map(|i| ScrapeError::Parse {
+                path: format!("/de/kalender/{}/play", i),
+                message: "play has no title".to_string(),
+            })
+            .collect(),
+        ..ScrapeReport::default()
+    };
+    // a single odd play page is no reason to alert the admin
+    assert!(!report(1).needs_attention());
+    assert!(!report(2).needs_attention());
+    assert!(report(3).needs_attention());
+}
@@ -1,9 +1,53 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    chat_theaters (chat_id, theater_id) {
+        chat_id -> Int8,
+        theater_id -> Int4,
+    }
+}
+
 diesel::table! {
     chats (id) {
         id -> Int8,
         name -> Varchar,
+        language -> Varchar,
+        hide_archived -> Bool,
+        topic_categories -> Array<Nullable<Text>>,
+    }
+}
+
+diesel::table! {
+    follows (chat_id, person_id) {
+        chat_id -> Int8,
+        person_id -> Int4,
+    }
+}
+
+diesel::table! {
+    persons (id) {
+        id -> Int4,
+        name -> Varchar,
+        url -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    play_credits (play_id, person_id, role) {
+        play_id -> Int4,
+        person_id -> Int4,
+        role -> Varchar,
+        position -> Int4,
+    }
+}
+
+diesel::table! {
+    play_translations (play_id, language) {
+        play_id -> Int4,
+        language -> Varchar,
+        name -> Varchar,
+        description -> Varchar,
+        description_html -> Varchar,
     }
 }
 
@@ -15,6 +59,18 @@ diesel::table! {
         description -> Varchar,
         image_url -> Varchar,
         meta_info -> Varchar,
+        duration_minutes -> Nullable<Int4>,
+        intermission -> Nullable<Bool>,
+        language -> Nullable<Varchar>,
+        surtitles -> Nullable<Varchar>,
+        age_recommendation -> Nullable<Varchar>,
+        content_warnings -> Nullable<Varchar>,
+        content_hash -> Nullable<Varchar>,
+        last_seen_at -> Nullable<Timestamptz>,
+        archived_at -> Nullable<Timestamptz>,
+        theater_id -> Int4,
+        description_html -> Varchar,
+        category -> Varchar,
     }
 }
 
@@ -23,10 +79,24 @@ diesel::table! {
         id -> Int4,
         play_id -> Int4,
         webid -> Varchar,
-        location -> Varchar,
         url -> Varchar,
         start_time -> Timestamptz,
         ticket_url -> Text,
+        venue_id -> Nullable<Int4>,
+        end_time -> Nullable<Timestamptz>,
+        ticket_status -> Varchar,
+        presale_date -> Nullable<Date>,
+        tags -> Array<Nullable<Text>>,
+        removed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    theaters (id) {
+        id -> Int4,
+        slug -> Varchar,
+        name -> Varchar,
+        base_url -> Varchar,
     }
 }
 
@@ -38,16 +108,39 @@ diesel::table! {
         last_updated -> Timestamptz,
         pinned_message_id -> Int4,
         pinned_message_hash -> Int8,
+        closed -> Bool,
+    }
+}
+
+diesel::table! {
+    venues (id) {
+        id -> Int4,
+        name -> Varchar,
     }
 }
 
+diesel::joinable!(chat_theaters -> chats (chat_id));
+diesel::joinable!(chat_theaters -> theaters (theater_id));
+diesel::joinable!(follows -> persons (person_id));
+diesel::joinable!(play_credits -> persons (person_id));
+diesel::joinable!(play_credits -> plays (play_id));
+diesel::joinable!(play_translations -> plays (play_id));
+diesel::joinable!(plays -> theaters (theater_id));
 diesel::joinable!(screenings -> plays (play_id));
+diesel::joinable!(screenings -> venues (venue_id));
 diesel::joinable!(topics -> chats (chat_id));
 diesel::joinable!(topics -> plays (play_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    chat_theaters,
     chats,
+    follows,
+    persons,
+    play_credits,
+    play_translations,
     plays,
     screenings,
+    theaters,
     topics,
+    venues,
 );
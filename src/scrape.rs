@@ -1,153 +1,2161 @@
+use crate::models::Credit;
+use crate::models::EventCategory;
+use crate::models::Language;
+use crate::models::PlayTranslation;
 use crate::models::PlayWithScreenings;
 use crate::models::Screening;
-use anyhow::{anyhow, Context, Result};
-use chrono::NaiveDateTime;
+use crate::models::ScreeningTag;
+use crate::models::ScreeningTags;
+use crate::models::TicketStatus;
+use crate::models::CAST_ROLE;
+use anyhow::{Context, Result};
+use chrono::{LocalResult, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::{Europe::Zurich, Tz};
+use futures::stream::{self, StreamExt};
+use ical::property::Property;
 use lazy_static::lazy_static;
-use log::error;
-use reqwest;
+use log::{error, warn};
+use regex::Regex;
+#[cfg(test)]
+use rstest::rstest;
 use scraper::ElementRef;
 use scraper::{Html, Selector};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::error::Error;
 #[allow(unused_imports)]
 use std::fs::File;
+use std::future::Future;
+use std::hash::Hasher;
 #[allow(unused_imports)]
 use std::io::Read;
-#[allow(unused_imports)]
-use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(test)]
+use time::macros::datetime;
 use time::OffsetDateTime;
 use time::UtcOffset;
+use time::{Date, Month};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::{sleep, Instant};
+
+// Number of months of the calendar that are scraped by default.
+pub const DEFAULT_HORIZON_MONTHS: u32 = 6;
+
+// Number of requests that are in flight at the same time by default.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+// Minimum time between two requests to the same host by default.
+pub const DEFAULT_REQUEST_DELAY: Duration = Duration::from_millis(250);
+
+// Time after which a request is given up by default.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Number of times a failed request is retried by default.
+pub const DEFAULT_RETRIES: u32 = 3;
+
+// Time before the first retry of a failed request by default.
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(1);
 
-pub const BASE_URL: &str = "https://www.schauspielhaus.ch";
+// User-Agent that the scraper sends by default.
+pub const DEFAULT_USER_AGENT: &str = concat!("schauspielhaus-bot/", env!("CARGO_PKG_VERSION"));
 
-// Prefix that all play titles have in common.
-pub const TITLE_PREFIX: &str = "Schauspielhaus Zürich: ";
+// Version of the play page parser. Plays whose page didn't change are not
+// parsed again, bump it when the parsing changes to update all plays.
+const PARSER_VERSION: u32 = 1;
+
+// Longest side of the thumbnails of play images in pixels, Telegram shows
+// photos at most this large.
+pub const THUMBNAIL_SIZE: u32 = 1280;
+
+// JPEG quality of the thumbnails of play images.
+const THUMBNAIL_QUALITY: u8 = 85;
 
 lazy_static! {
+    // Labels of special screenings, a screening can have several of them.
+    static ref SCREENING_TAGS: Vec<(ScreeningTag, Regex)> = vec![
+        // "Premierenverschiebung" is a postponed premiere and not a premiere
+        (ScreeningTag::Premiere, Regex::new(r"(?i)\b(premiere|uraufführung)\b").unwrap()),
+        (ScreeningTag::LastShow, Regex::new(r"(?i)derni[eè]re|letzte vorstellung|last performance").unwrap()),
+        (ScreeningTag::Introduction, Regex::new(r"(?i)einführung|introduction").unwrap()),
+        (ScreeningTag::Talk, Regex::new(r"(?i)gespräch|\btalk\b").unwrap()),
+        (ScreeningTag::Surtitles, Regex::new(r"(?i)übertitel|surtitles").unwrap()),
+        (ScreeningTag::Relaxed, Regex::new(r"(?i)relaxed").unwrap()),
+    ];
+    // Categories of events that aren't productions of the repertoire, the
+    // first that matches the calendar item wins. A talk after a screening is
+    // a screening tag, only talk series and readings are talks.
+    static ref EVENT_CATEGORIES: Vec<(EventCategory, Regex)> = vec![
+        (EventCategory::Young, Regex::new(r"(?i)junges schauspielhaus|klassenzimmer|für kinder|kinderkonzert|jugendliche|for children").unwrap()),
+        (EventCategory::Workshop, Regex::new(r"(?i)workshop|\bkurs\b|\bführung|guided tour|offene bühne|open stage").unwrap()),
+        (EventCategory::Concert, Regex::new(r"(?i)konzert|concert").unwrap()),
+        (EventCategory::Talk, Regex::new(r"(?i)gesprächsreihe|gespräch mit|lesung|\bliest\b|reading|vortrag|lecture|podium|poetry slam").unwrap()),
+        (EventCategory::GuestPerformance, Regex::new(r"(?i)gastspiel|guest performance|\bzu gast\b|inszenierung (des|der|vom)|übernahme vo[nm]").unwrap()),
+    ];
+    // Presale start dates like "Vorverkauf ab 1.10.2024" or "Vorverkaufsstart: 01.10.".
+    static ref PRESALE_DATE: Regex = Regex::new(r"(\d{1,2})\.\s*(\d{1,2})\.(\d{2,4})?").unwrap();
+}
+
+// Venues of the Schauspielhaus, spelled the way the calendar spells them.
+const KNOWN_VENUES: [&str; 6] = [
+    "Pfauen",
+    "Pfauen-Kammer",
+    "Schiffbau",
+    "Schiffbau-Halle",
+    "Schiffbau-Box",
+    "Schiffbau-Matchbox",
+];
+
+// venue_key reduces a venue name to lowercase words for comparison, dropping
+// the theatre name that the ICS files sometimes prepend.
+fn venue_key(name: &str) -> String {
+    name.to_lowercase()
+        .replace("schauspielhaus zürich", " ")
+        .replace(['-', ',', ':'], " ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+// normalize_venue maps the different spellings of a venue ("Schiffbau Halle",
+// "Halle", "Schauspielhaus Zürich, Pfauen") to one canonical name. Unknown
+// venues are returned with normalised whitespace.
+pub fn normalize_venue(raw: &str) -> String {
+    let key = venue_key(raw);
+    if let Some(venue) = KNOWN_VENUES.iter().find(|v| venue_key(v) == key) {
+        return venue.to_string();
+    }
+    // short forms like "Box" or "Matchbox"
+    let suffix = format!(" {}", key);
+    if let Some(venue) = KNOWN_VENUES
+        .iter()
+        .find(|v| !key.is_empty() && venue_key(v).ends_with(&suffix))
+    {
+        return venue.to_string();
+    }
+    raw.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("Pfauen", "Pfauen")]
+#[case("  Schiffbau-Box\n ", "Schiffbau-Box")]
+#[case("Schiffbau Halle", "Schiffbau-Halle")]
+#[case("Matchbox", "Schiffbau-Matchbox")]
+#[case("Box", "Schiffbau-Box")]
+#[case("Kammer", "Pfauen-Kammer")]
+#[case("Schauspielhaus Zürich, Pfauen", "Pfauen")]
+#[case("Schauspielhaus Zürich: Schiffbau-Halle", "Schiffbau-Halle")]
+#[case("Theater Neumarkt", "Theater Neumarkt")]
+#[case("", "")]
+fn test_normalize_venue(#[case] raw: &str, #[case] expected: &str) {
+    assert_eq!(normalize_venue(raw), expected);
+}
+
+// ScrapeError is why a page, ICS file or screening row could not be scraped.
+#[derive(Debug)]
+pub enum ScrapeError {
+    // The request took longer than the timeout.
+    Timeout {
+        path: String,
+    },
+    // The connection could not be established or broke off.
+    Connection {
+        path: String,
+        source: reqwest::Error,
+    },
+    // The website answered with an error status.
+    Status {
+        path: String,
+        status: u16,
+    },
+    // Reading or writing a local file (fixture, recording) failed.
+    Io {
+        path: String,
+        source: anyhow::Error,
+    },
+    // The content doesn't look like the scraper expects.
+    Parse {
+        path: String,
+        message: String,
+    },
+}
+
+impl ScrapeError {
+    fn from_reqwest(path: &str, e: reqwest::Error) -> ScrapeError {
+        let path = path.to_string();
+        match e.status() {
+            Some(status) => ScrapeError::Status {
+                path,
+                status: status.as_u16(),
+            },
+            None if e.is_timeout() => ScrapeError::Timeout { path },
+            None => ScrapeError::Connection { path, source: e },
+        }
+    }
+
+    // path is the path of the page or ICS file that failed.
+    pub fn path(&self) -> &str {
+        match self {
+            ScrapeError::Timeout { path }
+            | ScrapeError::Connection { path, .. }
+            | ScrapeError::Status { path, .. }
+            | ScrapeError::Io { path, .. }
+            | ScrapeError::Parse { path, .. } => path,
+        }
+    }
+
+    // kind is a short description of the error, for summaries.
+    pub fn kind(&self) -> String {
+        match self {
+            ScrapeError::Timeout { .. } => "timeout".to_string(),
+            ScrapeError::Connection { .. } => "connection error".to_string(),
+            ScrapeError::Status { status, .. } => format!("HTTP {}", status),
+            ScrapeError::Io { .. } => "I/O error".to_string(),
+            ScrapeError::Parse { .. } => "parse error".to_string(),
+        }
+    }
+
+    // is_transient tells whether the request may succeed when it is retried.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ScrapeError::Timeout { .. } | ScrapeError::Connection { .. } => true,
+            ScrapeError::Status { status, .. } => *status >= 500 || *status == 429,
+            ScrapeError::Io { .. } | ScrapeError::Parse { .. } => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ScrapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ScrapeError::Timeout { path } => write!(f, "timeout requesting {}", path),
+            ScrapeError::Connection { path, source } => {
+                write!(f, "connection error requesting {}: {}", path, source)
+            }
+            ScrapeError::Status { path, status } => {
+                write!(f, "HTTP status {} requesting {}", status, path)
+            }
+            ScrapeError::Io { path, source } => write!(f, "{}: {:#}", path, source),
+            ScrapeError::Parse { path, message } => {
+                write!(f, "error parsing {}: {}", path, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScrapeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScrapeError::Connection { source, .. } => Some(source),
+            ScrapeError::Io { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// failure_summary describes the errors of a scrape run, grouped by their kind.
+pub fn failure_summary(errors: &[ScrapeError]) -> String {
+    let mut kinds: BTreeMap<String, usize> = BTreeMap::new();
+    for e in errors {
+        *kinds.entry(e.kind()).or_default() += 1;
+    }
+    let mut summary = format!(
+        "{} failed: {}",
+        errors.len(),
+        kinds
+            .iter()
+            .map(|(kind, n)| format!("{} {}", n, kind))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    for e in errors {
+        summary.push_str(&format!("\n  {}", e));
+    }
+    summary
+}
+
+#[test]
+fn test_failure_summary() {
+    let errors = vec![
+        ScrapeError::Status {
+            path: "/de/kalender/1/a".to_string(),
+            status: 503,
+        },
+        ScrapeError::Timeout {
+            path: "/de/kalender/2/b".to_string(),
+        },
+        ScrapeError::Status {
+            path: "/de/kalender/3/c".to_string(),
+            status: 404,
+        },
+    ];
+    assert_eq!(
+        failure_summary(&errors),
+        "3 failed: 1 HTTP 404, 1 HTTP 503, 1 timeout
+  HTTP status 503 requesting /de/kalender/1/a
+  timeout requesting /de/kalender/2/b
+  HTTP status 404 requesting /de/kalender/3/c"
+    );
+    assert!(errors[0].is_transient());
+    assert!(errors[1].is_transient());
+    assert!(!errors[2].is_transient());
+}
+
+// Fetch abstracts over where the scraper loads its pages from, so that the
+// whole pipeline can run against the live website or against recorded fixtures.
+pub trait Fetch: Sync {
+    // fetch returns the raw body of the resource at `path`, which is relative
+    // to the site root (e.g. `/de/kalender`).
+    fn fetch(&self, path: &str) -> impl Future<Output = Result<Vec<u8>, ScrapeError>> + Send;
+
+    // fetch_text is like fetch but decodes the body as UTF-8.
+    fn fetch_text(&self, path: &str) -> impl Future<Output = Result<String, ScrapeError>> + Send {
+        async move {
+            let body = self.fetch(path).await?;
+            String::from_utf8(body).map_err(|e| ScrapeError::Parse {
+                path: path.to_string(),
+                message: e.to_string(),
+            })
+        }
+    }
+
+    // concurrency is the number of resources the scraper fetches at the same
+    // time. The results are still collected in the order of the pages.
+    fn concurrency(&self) -> usize {
+        DEFAULT_CONCURRENCY
+    }
+}
+
+// FetchOptions configures how HttpFetcher talks to the website.
+#[derive(Debug, Clone)]
+pub struct FetchOptions {
+    // Maximum number of requests in flight.
+    pub concurrency: usize,
+    // Minimum time between the start of two requests to the same host.
+    pub delay: Duration,
+    pub user_agent: String,
+    // Directory of the HTTP cache, pages are downloaded every time without it.
+    pub cache_dir: Option<PathBuf>,
+    // Time after which a request is given up.
+    pub timeout: Duration,
+    // Number of times a request is retried after a transient failure, the
+    // first retry waits retry_backoff and every further retry twice as long.
+    pub retries: u32,
+    pub retry_backoff: Duration,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        FetchOptions {
+            concurrency: DEFAULT_CONCURRENCY,
+            delay: DEFAULT_REQUEST_DELAY,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            cache_dir: None,
+            timeout: DEFAULT_TIMEOUT,
+            retries: DEFAULT_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+}
+
+// CachedResponse is a response in the HTTP cache. The body is stored at the
+// fixture_path of the page and the validators next to it.
+#[derive(Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    #[serde(skip)]
+    body: Vec<u8>,
+}
+
+// validators_path returns the file that stores the validators of a cached page.
+fn validators_path(dir: &Path, path: &str) -> PathBuf {
+    let mut file = fixture_path(dir, path).into_os_string();
+    file.push(".validators.json");
+    PathBuf::from(file)
+}
+
+// load_cached returns the cached response for `path`, if there is one.
+async fn load_cached(dir: &Path, path: &str) -> Option<CachedResponse> {
+    let validators = tokio::fs::read(validators_path(dir, path)).await.ok()?;
+    let mut cached: CachedResponse = serde_json::from_slice(&validators).ok()?;
+    cached.body = tokio::fs::read(fixture_path(dir, path)).await.ok()?;
+    Some(cached)
+}
+
+// store_cached writes a response into the cache. The validators are written
+// last, so an interrupted write leaves no usable entry behind.
+async fn store_cached(dir: &Path, path: &str, cached: &CachedResponse) -> Result<()> {
+    let file = fixture_path(dir, path);
+    if let Some(parent) = file.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("creating cache directory {}", parent.display()))?;
+    }
+    tokio::fs::write(&file, &cached.body)
+        .await
+        .with_context(|| format!("writing cache {}", file.display()))?;
+    tokio::fs::write(validators_path(dir, path), serde_json::to_vec(cached)?)
+        .await
+        .with_context(|| format!("writing cache validators for {}", file.display()))?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cached_response() {
+    let dir = std::env::temp_dir().join(format!("schauspielhaus-cache-{}", std::process::id()));
+    let path = "/de/kalender?month=2025-01";
+    assert_eq!(load_cached(&dir, path).await, None);
+    let cached = CachedResponse {
+        etag: Some("\"abc\"".to_string()),
+        last_modified: None,
+        body: b"<html></html>".to_vec(),
+    };
+    store_cached(&dir, path, &cached).await.unwrap();
+    let loaded = load_cached(&dir, path).await;
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(loaded, Some(cached));
+}
+
+// HttpFetcher fetches pages from the live website. Clones share the
+// concurrency limit and the request delay. With a cache directory it sends
+// conditional requests and reuses the cached body of unchanged pages.
+#[derive(Clone)]
+pub struct HttpFetcher {
+    base_url: String,
+    client: reqwest::Client,
+    options: FetchOptions,
+    permits: Arc<Semaphore>,
+    // time at which the next request to a host may start
+    next_request: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: &str) -> Self {
+        HttpFetcher::with_options(base_url, FetchOptions::default())
+    }
+
+    pub fn with_options(base_url: &str, options: FetchOptions) -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent(options.user_agent.clone())
+            .timeout(options.timeout)
+            .build()
+            .expect("Error building HTTP client");
+        HttpFetcher::with_client(base_url, client, options)
+    }
+
+    // with_client uses `client` for the requests, the user agent and the
+    // timeout of `options` are left to the client.
+    pub fn with_client(base_url: &str, client: reqwest::Client, options: FetchOptions) -> Self {
+        HttpFetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+            permits: Arc::new(Semaphore::new(options.concurrency.max(1))),
+            options,
+            next_request: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    // wait_for_host waits until a request to `host` may start and reserves
+    // the next slot, so that concurrent requests are spaced by the delay.
+    async fn wait_for_host(&self, host: &str) {
+        let start = {
+            let mut next_request = self.next_request.lock().await;
+            let now = Instant::now();
+            let start = next_request.get(host).map_or(now, |next| (*next).max(now));
+            next_request.insert(host.to_string(), start + self.options.delay);
+            start
+        };
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+impl Default for HttpFetcher {
+    fn default() -> Self {
+        HttpFetcher::new(Schauspielhaus::BASE_URL)
+    }
+}
+
+impl Fetch for HttpFetcher {
+    async fn fetch(&self, path: &str) -> Result<Vec<u8>, ScrapeError> {
+        let cached = match &self.options.cache_dir {
+            Some(dir) => load_cached(dir, path).await,
+            None => None,
+        };
+        let mut attempt = 0;
+        loop {
+            match self.fetch_once(path, cached.as_ref()).await {
+                Err(e) if e.is_transient() && attempt < self.options.retries => {
+                    let backoff = self.options.retry_backoff * 2u32.pow(attempt);
+                    warn!("{}, retrying in {:?}", e, backoff);
+                    sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn concurrency(&self) -> usize {
+        self.options.concurrency.max(1)
+    }
+}
+
+impl HttpFetcher {
+    // fetch_once requests `path` once, conditionally if it is cached.
+    async fn fetch_once(
+        &self,
+        path: &str,
+        cached: Option<&CachedResponse>,
+    ) -> Result<Vec<u8>, ScrapeError> {
+        let url = format!("{}{}", self.base_url, path);
+        let host = url::Url::parse(&url)
+            .map_err(|e| ScrapeError::Parse {
+                path: path.to_string(),
+                message: format!("invalid url {}: {}", url, e),
+            })?
+            .host_str()
+            .unwrap_or_default()
+            .to_string();
+        let mut request = self.client.get(&url);
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+        self.wait_for_host(&host).await;
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ScrapeError::from_reqwest(path, e))?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                return Ok(cached.body.clone());
+            }
+        }
+        let response = response
+            .error_for_status()
+            .map_err(|e| ScrapeError::from_reqwest(path, e))?;
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let mut fresh = CachedResponse {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+            body: vec![],
+        };
+        fresh.body = response
+            .bytes()
+            .await
+            .map_err(|e| ScrapeError::from_reqwest(path, e))?
+            .to_vec();
+        if let Some(dir) = &self.options.cache_dir {
+            if fresh.etag.is_some() || fresh.last_modified.is_some() {
+                // the page was downloaded, a broken cache only costs a request
+                if let Err(e) = store_cached(dir, path, &fresh).await {
+                    error!("Error caching {}: {:?}", url, e);
+                }
+            }
+        }
+        Ok(fresh.body)
+    }
+}
+
+// serve answers one request per connection with the given responses in order
+// and returns the received requests in lowercase.
+#[cfg(test)]
+async fn serve(responses: Vec<&'static str>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let mut requests = vec![];
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let n = socket.read(&mut request).await.unwrap();
+            requests.push(String::from_utf8_lossy(&request[..n]).to_lowercase());
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+        requests
+    });
+    (base_url, server)
+}
+
+#[cfg(test)]
+fn test_fetch_options() -> FetchOptions {
+    FetchOptions {
+        delay: Duration::ZERO,
+        retry_backoff: Duration::from_millis(1),
+        ..FetchOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn test_http_fetcher_cache() {
+    let (base_url, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\netag: \"v1\"\r\ncontent-length: 5\r\nconnection: close\r\n\r\nhello",
+        "HTTP/1.1 304 Not Modified\r\nconnection: close\r\n\r\n",
+    ])
+    .await;
+    let dir = std::env::temp_dir().join(format!("schauspielhaus-http-{}", std::process::id()));
+    let fetcher = HttpFetcher::with_options(
+        &base_url,
+        FetchOptions {
+            cache_dir: Some(dir.clone()),
+            ..test_fetch_options()
+        },
+    );
+    let first = fetcher.fetch_text("/de/kalender").await.unwrap();
+    let second = fetcher.fetch_text("/de/kalender").await.unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!((first.as_str(), second.as_str()), ("hello", "hello"));
+    let requests = server.await.unwrap();
+    assert!(!requests[0].contains("if-none-match"));
+    assert!(requests[1].contains("if-none-match: \"v1\""));
+}
+
+#[tokio::test]
+async fn test_http_fetcher_retries() {
+    let (base_url, server) = serve(vec![
+        "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\ncontent-length: 5\r\nconnection: close\r\n\r\nhello",
+        "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+    ])
+    .await;
+    let fetcher = HttpFetcher::with_options(&base_url, test_fetch_options());
+    // the 503 is retried
+    assert_eq!(fetcher.fetch_text("/a").await.unwrap(), "hello");
+    // the 404 is not
+    match fetcher.fetch_text("/b").await {
+        Err(ScrapeError::Status { path, status }) => {
+            assert_eq!((path.as_str(), status), ("/b", 404))
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert_eq!(server.await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_http_fetcher_timeout() {
+    // a server that accepts the connection but never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let fetcher = HttpFetcher::with_options(
+        &base_url,
+        FetchOptions {
+            timeout: Duration::from_millis(50),
+            retries: 1,
+            ..test_fetch_options()
+        },
+    );
+    match fetcher.fetch("/a").await {
+        Err(ScrapeError::Timeout { path }) => assert_eq!(path, "/a"),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_http_fetcher_delay() {
+    let fetcher = HttpFetcher::with_options(
+        Schauspielhaus::BASE_URL,
+        FetchOptions {
+            delay: Duration::from_secs(1),
+            ..FetchOptions::default()
+        },
+    );
+    let start = Instant::now();
+    fetcher.wait_for_host("www.schauspielhaus.ch").await;
+    fetcher.wait_for_host("www.schauspielhaus.ch").await;
+    fetcher.wait_for_host("example.com").await;
+    fetcher.wait_for_host("www.schauspielhaus.ch").await;
+    assert_eq!(start.elapsed(), Duration::from_secs(2));
+}
+
+// ReplayFetcher serves pages that were previously recorded into a directory,
+// see fixture_path for the layout.
+#[derive(Clone)]
+pub struct ReplayFetcher {
+    dir: PathBuf,
+}
+
+impl ReplayFetcher {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        ReplayFetcher { dir: dir.into() }
+    }
+}
+
+impl Fetch for ReplayFetcher {
+    async fn fetch(&self, path: &str) -> Result<Vec<u8>, ScrapeError> {
+        let file = fixture_path(&self.dir, path);
+        tokio::fs::read(&file).await.map_err(|e| ScrapeError::Io {
+            path: path.to_string(),
+            source: anyhow::Error::new(e).context(format!("reading fixture {}", file.display())),
+        })
+    }
+}
+
+// RecordingFetcher wraps another fetcher and stores every resource it fetches
+// in a directory, using the layout that ReplayFetcher reads.
+#[derive(Clone)]
+pub struct RecordingFetcher<F> {
+    inner: F,
+    dir: PathBuf,
+}
+
+impl<F: Fetch> RecordingFetcher<F> {
+    pub fn new(inner: F, dir: impl Into<PathBuf>) -> Self {
+        RecordingFetcher {
+            inner,
+            dir: dir.into(),
+        }
+    }
+}
+
+impl<F: Fetch> Fetch for RecordingFetcher<F> {
+    async fn fetch(&self, path: &str) -> Result<Vec<u8>, ScrapeError> {
+        let body = self.inner.fetch(path).await?;
+        let file = fixture_path(&self.dir, path);
+        let write = async {
+            if let Some(parent) = file.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("creating fixture directory {}", parent.display()))?;
+            }
+            tokio::fs::write(&file, &body)
+                .await
+                .with_context(|| format!("writing fixture {}", file.display()))
+        };
+        write.await.map_err(|source| ScrapeError::Io {
+            path: path.to_string(),
+            source,
+        })?;
+        Ok(body)
+    }
+
+    fn concurrency(&self) -> usize {
+        self.inner.concurrency()
+    }
+}
+
+#[tokio::test]
+async fn test_record_fixtures() {
+    let dir = std::env::temp_dir().join(format!("schauspielhaus-fixtures-{}", std::process::id()));
+    let recorded = replay_scraper().record_fixtures(&dir).await.unwrap();
+    assert_eq!(recorded, 3);
+    let replayed = Scraper::builder(Schauspielhaus)
+        .build_with_fetcher(ReplayFetcher::new(&dir))
+        .plays()
+        .await
+        .unwrap();
+    let original = replay_scraper().plays().await.unwrap();
+    // the screenings are read from the rows of the play page
+    let ics_recorded = dir
+        .join("de/kalender/30546/liebe-einfach-ausserirdisch/31436.ics")
+        .exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(!ics_recorded);
+    assert_eq!(
+        serde_json::to_string(&replayed["/de/kalender/30546/liebe-einfach-ausserirdisch"]).unwrap(),
+        serde_json::to_string(&original["/de/kalender/30546/liebe-einfach-ausserirdisch"]).unwrap()
+    );
+}
+
+// fixture_path maps a site path to the file that stores it inside a fixture
+// directory. Files with an extension (e.g. `.ics`) are stored as is, pages are
+// stored as `index.html` inside a directory named after the path:
+//
+//   /de/kalender                    -> <dir>/de/kalender/index.html
+//   /de/kalender?month=2025-01      -> <dir>/de/kalender/month=2025-01/index.html
+//   /de/kalender/30546/play         -> <dir>/de/kalender/30546/play/index.html
+//   /de/kalender/30546/play/1.ics   -> <dir>/de/kalender/30546/play/1.ics
+pub fn fixture_path(dir: &Path, path: &str) -> PathBuf {
+    let relative = path.trim_matches('/').replacen('?', "/", 1);
+    let file = dir.join(&relative);
+    match Path::new(&relative).extension() {
+        Some(_) => file,
+        None => file.join("index.html"),
+    }
+}
+
+#[test]
+fn test_fixture_path() {
+    let dir = Path::new("fixtures");
+    assert_eq!(
+        fixture_path(dir, "/de/kalender"),
+        Path::new("fixtures/de/kalender/index.html")
+    );
+    assert_eq!(
+        fixture_path(dir, "/de/kalender?month=2025-01"),
+        Path::new("fixtures/de/kalender/month=2025-01/index.html")
+    );
+    assert_eq!(
+        fixture_path(
+            dir,
+            "/de/kalender/30546/liebe-einfach-ausserirdisch/31436.ics"
+        ),
+        Path::new("fixtures/de/kalender/30546/liebe-einfach-ausserirdisch/31436.ics")
+    );
+}
+
+// Directory with a recorded copy of (a part of) the website used by the tests.
+#[cfg(test)]
+const REPLAY_DIR: &str = "testdata/replay";
+
+// CalendarMonth is a month of the calendar, as in `data-month="2024-09"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct CalendarMonth {
+    year: i32,
+    month: u32,
+}
+
+impl CalendarMonth {
+    fn parse(s: &str) -> Option<CalendarMonth> {
+        let (year, month) = s.trim().split_once('-')?;
+        let month = month.parse().ok().filter(|m| (1..=12).contains(m))?;
+        Some(CalendarMonth {
+            year: year.parse().ok()?,
+            month,
+        })
+    }
+
+    fn plus(&self, months: u32) -> CalendarMonth {
+        let index = self.year * 12 + (self.month as i32 - 1) + months as i32;
+        CalendarMonth {
+            year: index.div_euclid(12),
+            month: index.rem_euclid(12) as u32 + 1,
+        }
+    }
+}
+
+impl std::fmt::Display for CalendarMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+// calendar_months returns the months listed in the month navigation of a
+// calendar page, in order.
+fn calendar_months(html_content: &str) -> Vec<CalendarMonth> {
+    let fragment = Html::parse_document(html_content);
+    let mut months = fragment
+        .select(&Schauspielhaus::selectors().calendar_month)
+        .filter_map(|element| element.value().attr("data-month"))
+        .filter_map(CalendarMonth::parse)
+        .collect::<Vec<CalendarMonth>>();
+    months.sort();
+    months.dedup();
+    months
+}
+
+// download_calendar downloads the calendar pages covering `horizon_months`
+// months, starting with the current month. A calendar page shows a few months
+// and lists them in its month navigation, the following months are requested
+// with the month query parameter until the horizon is reached. A page that
+// fails ends the calendar, it is returned along with the pages before it.
+pub async fn download_calendar(
+    fetcher: &impl Fetch,
+    horizon_months: u32,
+) -> Result<(Vec<String>, Vec<ScrapeError>)> {
+    let first_page = fetcher
+        .fetch_text(Schauspielhaus::CALENDAR_PATH)
+        .await
+        .context("loading main calendar page")?;
+    let mut shown = calendar_months(&first_page);
+    let mut pages = vec![first_page];
+    let last_month = match shown.first() {
+        Some(first) => first.plus(horizon_months.saturating_sub(1)),
+        // without month navigation there is nothing to follow
+        None => return Ok((pages, vec![])),
+    };
+    while let Some(next) = shown.last().map(|m| m.plus(1)) {
+        if next > last_month {
+            break;
+        }
+        let path = format!(
+            "{}?{}={}",
+            Schauspielhaus::CALENDAR_PATH,
+            Schauspielhaus::CALENDAR_MONTH_PARAM,
+            next
+        );
+        let page = match fetcher.fetch_text(&path).await {
+            Ok(p) => p,
+            // the months that were loaded are still useful
+            Err(e) => return Ok((pages, vec![e])),
+        };
+        let months = calendar_months(&page);
+        // stop at the end of the season, when no later months are shown
+        if months.last() <= shown.last() {
+            break;
+        }
+        shown = months;
+        pages.push(page);
+    }
+    Ok((pages, vec![]))
+}
+
+#[tokio::test]
+async fn test_download_calendar() {
+    let fetcher = ReplayFetcher::new(REPLAY_DIR);
+    // the first page shows September to December
+    let (pages, failures) = download_calendar(&fetcher, 4).await.unwrap();
+    assert_eq!(pages.len(), 1);
+    assert!(failures.is_empty());
+    assert_eq!(find_plays(&pages[0]).len(), 2);
+    // January is on the next page, there is no page for May in the fixtures
+    let (pages, failures) = download_calendar(&fetcher, 12).await.unwrap();
+    assert_eq!(pages.len(), 2);
+    assert_eq!(
+        failures.iter().map(|e| e.path()).collect::<Vec<&str>>(),
+        vec!["/de/kalender?month=2025-05"]
+    );
+    assert_eq!(
+        find_plays(&pages[1])
+            .into_iter()
+            .map(|p| p.url)
+            .collect::<Vec<String>>(),
+        vec![
+            "/de/kalender/30546/liebe-einfach-ausserirdisch",
+            "/de/kalender/32104/der-kirschgarten"
+        ]
+    );
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("2024-09", 0, "2024-09")]
+#[case("2024-09", 3, "2024-12")]
+#[case("2024-09", 4, "2025-01")]
+#[case("2024-12", 13, "2026-01")]
+fn test_calendar_month(#[case] month: &str, #[case] plus: u32, #[case] expected: &str) {
+    assert_eq!(
+        CalendarMonth::parse(month).unwrap().plus(plus).to_string(),
+        expected
+    );
+}
+
+// CalendarEntry is a play linked from the calendar, with the category of the
+// event read from its calendar item.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct CalendarEntry {
+    pub url: String,
+    pub category: EventCategory,
+}
+
+// find_plays returns the plays linked from the calendar page, in the order in
+// which they first appear.
+fn find_plays(html_content: &str) -> Vec<CalendarEntry> {
+    let fragment = Html::parse_document(html_content);
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut plays: Vec<CalendarEntry> = Vec::new();
+    for item in fragment.select(&Schauspielhaus::selectors().calendar_item) {
+        let element = match item
+            .select(&Schauspielhaus::selectors().play_calendar_titles)
+            .next()
+        {
+            Some(e) => e,
+            None => continue,
+        };
+        let raw_name = element.inner_html();
+        let name = raw_name.trim();
+        let url = match element.value().attr("href") {
+            Some(url) => url.to_string(),
+            None => {
+                error!("No href attribute found for element {}", name);
+                continue;
+            }
+        };
+        if seen.insert(url.clone()) {
+            let text = item
+                .select(&Schauspielhaus::selectors().calendar_item_text)
+                .flat_map(|e| e.text())
+                .chain(std::iter::once(name))
+                .collect::<Vec<&str>>()
+                .join(" ");
+            plays.push(CalendarEntry {
+                url,
+                category: event_category(&text),
+            });
+        }
+    }
+    plays
+}
+
+// event_category returns the category of the event described by the text of
+// its calendar item, a production unless the text says otherwise.
+fn event_category(text: &str) -> EventCategory {
+    let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+    EVENT_CATEGORIES
+        .iter()
+        .find(|(_, re)| re.is_match(&text))
+        .map(|(category, _)| *category)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("von William Shakespeare Regie: Anne Lenk", EventCategory::Production)]
+#[case(
+    "Premiere Ein Projekt zum Thema Cybermobbing Klassenzimmerstück empfohlen ab 13 Jahren",
+    EventCategory::Young
+)]
+#[case("Ferienkurs für Kinder zwischen 8 und 10 Jahren", EventCategory::Young)]
+#[case("Öffentliche Führung: Schiffbau", EventCategory::Workshop)]
+#[case(
+    "Grosses Theaterkonzert von Ensemble und Mitarbeitenden",
+    EventCategory::Concert
+)]
+#[case(
+    "Neue Gesprächsreihe Reden über soziale Unterschiede Zu Gast MADELEINE HERZOG",
+    EventCategory::Talk
+)]
+#[case("zu Gast Axel Hacke liest und erzählt", EventCategory::Talk)]
+#[case("mit Publikumsgespräch von Dea Loher", EventCategory::Production)]
+#[case(
+    "mit Einführung Nach dem Roman von Kim de l’Horizon",
+    EventCategory::Production
+)]
+#[case(
+    "von René Pollesch Inszenierung des Deutschen Theaters Berlin",
+    EventCategory::GuestPerformance
+)]
+#[case(
+    "Ein Abend von und mit Bruno Cathomas Übernahme vom Theater Chur",
+    EventCategory::GuestPerformance
+)]
+fn test_event_category(#[case] text: &str, #[case] expected: EventCategory) {
+    assert_eq!(event_category(text), expected);
+}
+
+#[test]
+fn test_find_plays() {
+    let mut file = File::open("src/testdata/calendar.html").unwrap();
+    let mut html_content = String::new();
+    file.read_to_string(&mut html_content).unwrap();
+    let plays = find_plays(&html_content);
+    // json marshall plays to string with indentation
+    let plays_json = serde_json::to_string_pretty(&plays).unwrap();
+    goldie::assert!(plays_json);
+}
+
+// Scraper scrapes the programme of a theater, see Scraper::builder.
+pub struct Scraper<S, F> {
+    source: S,
+    fetcher: F,
+    // The plays are translated into this language if their pages are in
+    // another one.
+    language: Language,
+    // Maximum number of play pages that are scraped at the same time.
+    concurrency: usize,
+    // Number of months of the programme that are scraped.
+    horizon_months: u32,
+}
+
+// ScraperBuilder configures a Scraper. By default it fetches the website of
+// the source over HTTP with the default FetchOptions, translates the plays
+// into English and scrapes DEFAULT_HORIZON_MONTHS months. The language is the
+// one the plays are translated into, not the one they are shown in: chats
+// default to German, the language of the play pages, and the English
+// translation is scraped for the chats that switch to English.
+pub struct ScraperBuilder<S> {
+    source: S,
+    base_url: Option<String>,
+    client: Option<reqwest::Client>,
+    options: FetchOptions,
+    concurrency: Option<usize>,
+    language: Language,
+    horizon_months: u32,
+}
+
+impl<S: TheaterSource> Scraper<S, HttpFetcher> {
+    pub fn builder(source: S) -> ScraperBuilder<S> {
+        ScraperBuilder {
+            source,
+            base_url: None,
+            client: None,
+            options: FetchOptions::default(),
+            concurrency: None,
+            language: Language::En,
+            horizon_months: DEFAULT_HORIZON_MONTHS,
+        }
+    }
+}
+
+impl<S: TheaterSource> ScraperBuilder<S> {
+    // base_url is the website the pages are fetched from instead of the one of
+    // the source, e.g. a local mirror or a staging copy.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    // client is used for the requests instead of a client built from the
+    // user agent and the timeout of the fetch options.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn fetch_options(mut self, options: FetchOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    // concurrency overrides the one of the fetch options and of the fetcher.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn horizon_months(mut self, horizon_months: u32) -> Self {
+        self.horizon_months = horizon_months;
+        self
+    }
+
+    // build returns a Scraper that fetches the pages over HTTP.
+    pub fn build(mut self) -> Scraper<S, HttpFetcher> {
+        if let Some(concurrency) = self.concurrency {
+            self.options.concurrency = concurrency;
+        }
+        let base_url = self
+            .base_url
+            .clone()
+            .unwrap_or_else(|| self.source.base_url().to_string());
+        let fetcher = match self.client.take() {
+            Some(client) => HttpFetcher::with_client(&base_url, client, self.options.clone()),
+            None => HttpFetcher::with_options(&base_url, self.options.clone()),
+        };
+        self.build_with_fetcher(fetcher)
+    }
+
+    // build_with_fetcher returns a Scraper that fetches the pages with
+    // `fetcher`, e.g. a ReplayFetcher. The base url, the client and the fetch
+    // options are not used.
+    pub fn build_with_fetcher<F: Fetch>(self, fetcher: F) -> Scraper<S, F> {
+        Scraper {
+            concurrency: self.concurrency.unwrap_or(fetcher.concurrency()),
+            source: self.source,
+            fetcher,
+            language: self.language,
+            horizon_months: self.horizon_months,
+        }
+    }
+}
+
+impl<S: TheaterSource, F: Fetch> Scraper<S, F> {
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    // plays downloads the plays from the programme of the theater and returns
+    // a map url -> PlayWithScreenings. The play pages are fetched
+    // concurrently, the map keeps the output ordered.
+    pub async fn plays(&self) -> Result<BTreeMap<String, PlayWithScreenings>> {
+        let scraped = self.changed_plays(&HashMap::new()).await?;
+        if !scraped.failures.is_empty() {
+            error!("{}", failure_summary(&scraped.failures));
+        }
+        Ok(scraped.plays)
+    }
+
+    // changed_plays is like plays but skips parsing the plays whose page has
+    // the content hash in `known_hashes` (url -> hash), i.e. is unchanged
+    // since the previous run.
+    pub async fn changed_plays(
+        &self,
+        known_hashes: &HashMap<String, String>,
+    ) -> Result<ScrapedPlays> {
+        let (plays, calendar_failures) = self
+            .source
+            .discover_plays(&self.fetcher, self.horizon_months)
+            .await?;
+        let results = stream::iter(plays)
+            .map(|entry| async move {
+                let known_hash = known_hashes.get(&entry.url).map(String::as_str);
+                let result = scrape_play_if_changed(
+                    &self.source,
+                    &self.fetcher,
+                    &entry,
+                    known_hash,
+                    self.language,
+                )
+                .await;
+                (entry.url, result)
+            })
+            .buffered(self.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+        let mut scraped = ScrapedPlays {
+            listed: results.len(),
+            partial_calendar: !calendar_failures.is_empty(),
+            failures: calendar_failures,
+            ..ScrapedPlays::default()
+        };
+        for (play, result) in results {
+            match result {
+                Ok(Some(fetched)) => {
+                    scraped.screening_rows += fetched.screening_rows;
+                    scraped.ics_fallbacks += fetched.ics_fallbacks;
+                    scraped.failures.extend(fetched.failures);
+                    // a play that doesn't look right is not stored
+                    match validate_play(&play, &fetched.play) {
+                        Ok(()) => {
+                            if !fetched.screenings_complete {
+                                scraped.incomplete.insert(play.clone());
+                            }
+                            scraped.plays.insert(play, fetched.play);
+                        }
+                        Err(e) => scraped.failures.push(e),
+                    }
+                }
+                Ok(None) => {
+                    scraped.unchanged.insert(play);
+                }
+                Err(e) => scraped.failures.push(e),
+            }
+        }
+        Ok(scraped)
+    }
+
+    // play downloads and parses the play at `url`.
+    pub async fn play(
+        &self,
+        url: &str,
+    ) -> Result<(PlayWithScreenings, Vec<ScrapeError>), ScrapeError> {
+        let play_page_content = self.fetcher.fetch_text(url).await?;
+        Ok(self.play_from_page(url, &play_page_content).await)
+    }
+
+    // play_from_page parses a play page and fetches what the page links to.
+    // Screenings and translations that fail are left out of the play and
+    // returned along with it.
+    pub async fn play_from_page(
+        &self,
+        url: &str,
+        play_page_content: &str,
+    ) -> (PlayWithScreenings, Vec<ScrapeError>) {
+        scrape_play(
+            &self.source,
+            &self.fetcher,
+            url,
+            play_page_content,
+            self.language,
+        )
+        .await
+    }
+
+    // record_fixtures downloads the calendar, every play page and the ICS
+    // files of screenings that can't be read from their row into `dir`, so
+    // that the scrape can later be replayed with ReplayFetcher. It returns the
+    // number of plays that were recorded.
+    pub async fn record_fixtures(self, dir: &Path) -> Result<usize> {
+        let recorder = Scraper {
+            fetcher: RecordingFetcher::new(self.fetcher, dir),
+            source: self.source,
+            language: self.language,
+            concurrency: self.concurrency,
+            horizon_months: self.horizon_months,
+        };
+        let plays = recorder.plays().await?;
+        Ok(plays.len())
+    }
+
+    // mirror_images mirrors the images at `image_urls` into `dir` with the
+    // fetcher of the scraper, see mirror_image.
+    pub async fn mirror_images<'a>(
+        &self,
+        dir: &Path,
+        image_urls: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<ScrapeError> {
+        mirror_images(&self.fetcher, dir, image_urls).await
+    }
+}
+
+#[cfg(test)]
+fn replay_scraper() -> Scraper<Schauspielhaus, ReplayFetcher> {
+    Scraper::builder(Schauspielhaus).build_with_fetcher(ReplayFetcher::new(REPLAY_DIR))
+}
+
+#[test]
+fn test_scraper_builder() {
+    let scraper = Scraper::builder(Schauspielhaus).build();
+    assert_eq!(scraper.fetcher.base_url, Schauspielhaus::BASE_URL);
+    assert_eq!(scraper.concurrency, DEFAULT_CONCURRENCY);
+    assert_eq!(scraper.language, Language::En);
+    assert_eq!(scraper.horizon_months, DEFAULT_HORIZON_MONTHS);
+
+    let scraper = Scraper::builder(Schauspielhaus)
+        .base_url("http://localhost:8080/")
+        .client(reqwest::Client::new())
+        .concurrency(1)
+        .language(Language::De)
+        .horizon_months(2)
+        .build();
+    assert_eq!(scraper.fetcher.base_url, "http://localhost:8080");
+    assert_eq!(scraper.fetcher.options.concurrency, 1);
+    assert_eq!(scraper.concurrency, 1);
+    assert_eq!(scraper.language, Language::De);
+    assert_eq!(scraper.horizon_months, 2);
+}
+
+#[tokio::test]
+async fn test_scraper_language() {
+    let url = "/de/kalender/30546/liebe-einfach-ausserirdisch";
+    let (play, failures) = replay_scraper().play(url).await.unwrap();
+    assert_eq!(play.name(Language::En), "Love, Simply Extraterrestrial");
+    assert!(failures.is_empty());
+    // the English page is not fetched
+    let (play, failures) = Scraper::builder(Schauspielhaus)
+        .language(Language::De)
+        .build_with_fetcher(ReplayFetcher::new(REPLAY_DIR))
+        .play(url)
+        .await
+        .unwrap();
+    assert!(play.translations.is_empty());
+    assert!(failures.is_empty());
+}
+
+// ScrapedPlays are the plays found in the calendar.
+#[derive(Default)]
+pub struct ScrapedPlays {
+    // Plays that were parsed, keyed by url.
+    pub plays: BTreeMap<String, PlayWithScreenings>,
+    // Urls of plays whose page didn't change, they were not parsed.
+    pub unchanged: BTreeSet<String>,
+    // Calendar pages, play pages, screenings and translations that failed.
+    // Failed play pages are neither in plays nor in unchanged.
+    pub failures: Vec<ScrapeError>,
+    // Whether some calendar pages failed, the plays listed only on them are
+    // neither in plays nor in unchanged.
+    pub partial_calendar: bool,
+    // Number of plays in the calendar, the ones that failed are neither in
+    // plays nor in unchanged.
+    pub listed: usize,
+    // Number of screening rows of the parsed plays, and of the rows whose
+    // screening was read from its ICS file, see ParsedScreenings.
+    pub screening_rows: usize,
+    pub ics_fallbacks: usize,
+    // Urls of the plays in plays of which some screenings failed, their
+    // screenings that are not in the play may still be listed.
+    pub incomplete: BTreeSet<String>,
+}
+
+#[tokio::test]
+async fn test_changed_plays() {
+    // only some of the English pages are recorded, plays whose translation
+    // failed are always scraped, see test_changed_translation
+    let scraper = Scraper::builder(Schauspielhaus)
+        .language(Language::De)
+        .build_with_fetcher(ReplayFetcher::new(REPLAY_DIR));
+    let plays = scraper.plays().await.unwrap();
+    let mut known_hashes = plays
+        .iter()
+        .map(|(url, play)| (url.clone(), play.play.content_hash.clone().unwrap()))
+        .collect::<HashMap<String, String>>();
+    known_hashes.insert(
+        "/de/kalender/32104/der-kirschgarten".to_string(),
+        "outdated".to_string(),
+    );
+    let scraped = scraper.changed_plays(&known_hashes).await.unwrap();
+    assert_eq!(
+        scraped.plays.keys().collect::<Vec<&String>>(),
+        vec!["/de/kalender/32104/der-kirschgarten"]
+    );
+    assert_eq!(scraped.unchanged.len(), 2);
+
+    // a play that moved to another category in the calendar changed
+    let url = "/de/kalender/30546/liebe-einfach-ausserirdisch";
+    let play = &plays[url].play;
+    let entry = CalendarEntry {
+        url: url.to_string(),
+        category: EventCategory::Talk,
+    };
+    assert_ne!(play.category, entry.category);
+    let changed = scrape_play_if_changed(
+        &Schauspielhaus,
+        &scraper.fetcher,
+        &entry,
+        play.content_hash.as_deref(),
+        Language::De,
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(changed.play.play.category, EventCategory::Talk);
+}
+
+// TranslationFetcher serves the recorded pages, except for the English pages
+// which are `english_page`, or time out if it is None.
+#[cfg(test)]
+struct TranslationFetcher {
+    english_page: Option<&'static str>,
+}
+
+#[cfg(test)]
+impl Fetch for TranslationFetcher {
+    async fn fetch(&self, path: &str) -> Result<Vec<u8>, ScrapeError> {
+        if !path.starts_with("/en/") {
+            return ReplayFetcher::new(REPLAY_DIR).fetch(path).await;
+        }
+        match self.english_page {
+            Some(page) => Ok(page.as_bytes().to_vec()),
+            None => Err(ScrapeError::Timeout {
+                path: path.to_string(),
+            }),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_changed_translation() {
+    let scraper = |english_page| {
+        Scraper::builder(Schauspielhaus).build_with_fetcher(TranslationFetcher { english_page })
+    };
+    let known_hashes = scraper(Some("<h1>Cherry Orchard</h1>"))
+        .plays()
+        .await
+        .unwrap()
+        .iter()
+        .map(|(url, play)| (url.clone(), play.play.content_hash.clone().unwrap()))
+        .collect::<HashMap<String, String>>();
+    let unchanged = scraper(Some("<h1>Cherry Orchard</h1>"))
+        .changed_plays(&known_hashes)
+        .await
+        .unwrap();
+    assert!(unchanged.plays.is_empty());
+
+    // an edit of the English pages alone changes the plays
+    let scraped = scraper(Some("<h1>The Cherry Orchard</h1>"))
+        .changed_plays(&known_hashes)
+        .await
+        .unwrap();
+    assert_eq!(scraped.plays.len(), known_hashes.len());
+    assert!(scraped.unchanged.is_empty());
+
+    // a play whose translation failed has no content hash, so that it is
+    // scraped again by the next run
+    let scraped = scraper(None).changed_plays(&known_hashes).await.unwrap();
+    assert_eq!(scraped.plays.len(), known_hashes.len());
+    assert!(scraped
+        .plays
+        .values()
+        .all(|p| p.play.content_hash.is_none()));
+    assert_eq!(scraped.failures.len(), known_hashes.len());
+}
+
+// BrokenRowFetcher serves the recorded pages, the first screening row of the
+// play page at `url` has no calendar link.
+#[cfg(test)]
+struct BrokenRowFetcher {
+    url: &'static str,
+}
+
+#[cfg(test)]
+impl Fetch for BrokenRowFetcher {
+    async fn fetch(&self, path: &str) -> Result<Vec<u8>, ScrapeError> {
+        let page = ReplayFetcher::new(REPLAY_DIR).fetch(path).await?;
+        if path != self.url {
+            return Ok(page);
+        }
+        Ok(String::from_utf8(page)
+            .unwrap()
+            .replacen("activity-ticket__calendar", "activity-ticket__broken", 1)
+            .into_bytes())
+    }
+}
+
+#[tokio::test]
+async fn test_changed_plays_broken_row() {
+    let url = "/de/kalender/30546/liebe-einfach-ausserirdisch";
+    let scraper = |fetcher| {
+        Scraper::builder(Schauspielhaus)
+            .language(Language::De)
+            .build_with_fetcher(fetcher)
+    };
+    let complete = scraper(BrokenRowFetcher { url: "" })
+        .changed_plays(&HashMap::new())
+        .await
+        .unwrap();
+    assert!(complete.incomplete.is_empty());
+    assert_eq!(complete.listed, complete.plays.len());
+    assert_eq!(complete.ics_fallbacks, 0);
+
+    // a play with a screening that failed to parse is stored without it, and
+    // without a content hash so that it is scraped again by the next run
+    let scraped = scraper(BrokenRowFetcher { url })
+        .changed_plays(&HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(
+        scraped.incomplete.iter().collect::<Vec<&String>>(),
+        vec![url]
+    );
+    assert_eq!(
+        scraped.plays[url].screenings.len() + 1,
+        complete.plays[url].screenings.len()
+    );
+    assert!(scraped.plays[url].play.content_hash.is_none());
+    assert_eq!(scraped.screening_rows, complete.screening_rows);
+    assert_eq!(
+        scraped
+            .failures
+            .iter()
+            .map(|e| e.path())
+            .collect::<Vec<&str>>(),
+        vec![url]
+    );
+}
+
+#[tokio::test]
+async fn test_parse_screenings_ics_fallback() {
+    let url = "/de/kalender/30546/liebe-einfach-ausserirdisch";
+    let fetcher = ReplayFetcher::new(REPLAY_DIR);
+    let page = fetcher.fetch_text(url).await.unwrap();
+    let parsed = Schauspielhaus.parse_screenings(&fetcher, url, &page).await;
+    assert!(parsed.rows > 0);
+    assert_eq!(parsed.ics_fallbacks, 0);
+
+    // rows without a date are read from their ICS file
+    let page = page.replace("article-event__date-date", "article-event__date-moved");
+    let fallback = Schauspielhaus.parse_screenings(&fetcher, url, &page).await;
+    assert_eq!(fallback.ics_fallbacks, fallback.rows);
+    let times = |parsed: &ParsedScreenings| {
+        parsed
+            .screenings
+            .iter()
+            .map(|s| (s.webid.clone(), s.start_time))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(times(&fallback), times(&parsed));
+}
+
+#[tokio::test]
+async fn test_download_play() {
+    let fetcher = ReplayFetcher::new(REPLAY_DIR);
+    let play = &find_plays(&download_calendar(&fetcher, 1).await.unwrap().0[0])[1].url;
+    let play_page_content = fetcher.fetch_text(play).await.unwrap();
+    goldie::assert!(play_page_content);
+}
+
+#[tokio::test]
+async fn test_play_from_page() {
+    // read html from src/testdata/test_download_play.golden
+    let mut file = File::open("src/testdata/test_download_play.golden").unwrap();
+    let mut html_content = String::new();
+    file.read_to_string(&mut html_content).unwrap();
+    let (play, failures) = replay_scraper()
+        .play_from_page(
+            "/de/kalender/31446/poetry-slam-saisoneroeffnung",
+            &html_content,
+        )
+        .await;
+    // the English page of the play is not recorded
+    assert_eq!(
+        failures.iter().map(|e| e.path()).collect::<Vec<&str>>(),
+        vec!["/en/kalender/31446/poetry-slam-saisoneroeffnung"]
+    );
+    let play_json = serde_json::to_string_pretty(&play).unwrap();
+    goldie::assert!(play_json);
+}
+
+#[tokio::test]
+async fn test_validate_play() {
+    let url = "/de/kalender/31446/poetry-slam-saisoneroeffnung";
+    let html_content = std::fs::read_to_string("src/testdata/test_download_play.golden").unwrap();
+    let scraper = replay_scraper();
+    let (play, _) = scraper.play_from_page(url, &html_content).await;
+    assert!(validate_play(url, &play).is_ok());
+
+    // a redesign renames the classes the selectors match
+    let redesigned = html_content.replace("article__title", "hero__title");
+    let (play, _) = scraper.play_from_page(url, &redesigned).await;
+    assert_eq!(
+        validate_play(url, &play).unwrap_err().to_string(),
+        "error parsing /de/kalender/31446/poetry-slam-saisoneroeffnung: play has no title"
+    );
+    let redesigned = html_content.replace("article-event", "event-row");
+    let (play, _) = scraper.play_from_page(url, &redesigned).await;
+    assert_eq!(
+        validate_play(url, &play).unwrap_err().to_string(),
+        "error parsing /de/kalender/31446/poetry-slam-saisoneroeffnung: play has no screenings"
+    );
+}
+
+#[test]
+fn test_play_from_page_is_send() {
+    fn assert_send<T: Send>(_: T) {}
+    let scraper = replay_scraper();
+    assert_send(scraper.play_from_page("", ""));
+}
+
+// scrape_play is Scraper::play_from_page, it returns the failed screenings
+// and translations along with the play.
+async fn scrape_play(
+    source: &impl TheaterSource,
+    fetcher: &impl Fetch,
+    url: &str,
+    play_page_content: &str,
+    language: Language,
+) -> (PlayWithScreenings, Vec<ScrapeError>) {
+    let translation_page = fetch_translation(source, fetcher, play_page_content, language).await;
+    let fetched = parse_fetched_play(
+        source,
+        fetcher,
+        url,
+        EventCategory::default(),
+        play_page_content,
+        translation_page,
+    )
+    .await;
+    (fetched.play, fetched.failures)
+}
+
+// fetch_translation fetches the page of the play in `language`, if the play
+// page links to one, see TheaterSource::translation_url.
+async fn fetch_translation(
+    source: &impl TheaterSource,
+    fetcher: &impl Fetch,
+    play_page_content: &str,
+    language: Language,
+) -> Result<Option<String>, ScrapeError> {
+    match source.translation_url(play_page_content, language) {
+        Some(url) => Ok(Some(fetcher.fetch_text(&url).await?)),
+        None => Ok(None),
+    }
+}
+
+// FetchedPlay is a parsed play along with the pages that failed.
+struct FetchedPlay {
+    play: PlayWithScreenings,
+    failures: Vec<ScrapeError>,
+    // Whether none of the screenings of the play page failed.
+    screenings_complete: bool,
+    // See ParsedScreenings.
+    screening_rows: usize,
+    ics_fallbacks: usize,
+}
+
+// parse_fetched_play parses the play page and the page of its translation and
+// fetches the screenings. `category` is the one of the calendar entry of the
+// play. A play whose translation or some of whose screenings failed has no
+// content hash, so that it is scraped again by the next run.
+async fn parse_fetched_play(
+    source: &impl TheaterSource,
+    fetcher: &impl Fetch,
+    url: &str,
+    category: EventCategory,
+    play_page_content: &str,
+    translation_page: Result<Option<String>, ScrapeError>,
+) -> FetchedPlay {
+    let mut failures = vec![];
+    let content_hash = match &translation_page {
+        Ok(page) => Some(content_hash(play_page_content, page.as_deref(), category)),
+        Err(_) => None,
+    };
+    let translation_page = match translation_page {
+        Ok(page) => page,
+        // the play is still useful without a translation
+        Err(e) => {
+            failures.push(e);
+            None
+        }
+    };
+    let mut play = source.parse_play(url, play_page_content, translation_page.as_deref());
+    let parsed = source
+        .parse_screenings(fetcher, url, play_page_content)
+        .await;
+    play.play.url = url.to_string();
+    play.play.category = category;
+    let screenings_complete = parsed.failures.is_empty();
+    play.play.content_hash = content_hash.filter(|_| screenings_complete);
+    play.screenings = parsed.screenings;
+    failures.extend(parsed.failures);
+
+    // Screenings without an end in their ICS file end after the duration
+    // listed in the meta info.
+    if let Some(minutes) = play.play.duration_minutes {
+        for screening in play.screenings.iter_mut() {
+            if screening.end_time.is_none() {
+                screening.end_time =
+                    Some(screening.start_time + time::Duration::minutes(minutes.into()));
+            }
+        }
+    }
+
+    FetchedPlay {
+        play,
+        failures,
+        screenings_complete,
+        screening_rows: parsed.rows,
+        ics_fallbacks: parsed.ics_fallbacks,
+    }
+}
+
+// TheaterSource is the website of a theater that plays are scraped from.
+// Fetching the pages concurrently, skipping unchanged pages and validating the
+// plays is the same for all theaters and done by Scraper.
+pub trait TheaterSource: Sync {
+    // slug identifies the theater in the database and in commands.
+    fn slug(&self) -> &'static str;
+
+    fn name(&self) -> &'static str;
+
+    // base_url is the website the urls of the plays are relative to.
+    fn base_url(&self) -> &'static str;
+
+    // discover_plays returns the plays in the programme of the next
+    // `horizon_months` months, each url once, along with the calendar pages
+    // that failed. The plays of the failed pages are missing.
+    fn discover_plays(
+        &self,
+        fetcher: &impl Fetch,
+        horizon_months: u32,
+    ) -> impl Future<Output = Result<(Vec<CalendarEntry>, Vec<ScrapeError>)>> + Send;
+
+    // translation_url returns the url of the page of the play in `language`,
+    // or None if the play page is in `language` or doesn't link to one. The
+    // page is part of the content hash of the play.
+    fn translation_url(&self, play_page_content: &str, language: Language) -> Option<String>;
+
+    // parse_play parses the play at `url` without its screenings, and its
+    // translation from `translation_page`, the page at translation_url.
+    fn parse_play(
+        &self,
+        url: &str,
+        play_page_content: &str,
+        translation_page: Option<&str>,
+    ) -> PlayWithScreenings;
+
+    // parse_screenings parses the screenings of the play at `url` in the order
+    // of the play page, along with the screenings that failed.
+    fn parse_screenings(
+        &self,
+        fetcher: &impl Fetch,
+        url: &str,
+        play_page_content: &str,
+    ) -> impl Future<Output = ParsedScreenings> + Send;
+}
+
+// ParsedScreenings are the screenings of a play page.
+#[derive(Debug, Default)]
+pub struct ParsedScreenings {
+    pub screenings: Vec<Screening>,
+    // Rows and ICS files that failed, their screenings are missing.
+    pub failures: Vec<ScrapeError>,
+    // Number of screening rows on the page.
+    pub rows: usize,
+    // Number of rows whose date couldn't be read, their screening was read
+    // from its ICS file instead.
+    pub ics_fallbacks: usize,
+}
+
+// Schauspielhaus is the website of the Schauspielhaus Zürich, the programme is
+// the calendar and every play has a page with a row per screening.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Schauspielhaus;
+
+impl Schauspielhaus {
+    // Website of the Schauspielhaus, Scraper::builder can point the scraper at
+    // another copy of it.
+    pub const BASE_URL: &str = "https://www.schauspielhaus.ch";
+
+    // Path of the calendar page that lists all screenings.
+    const CALENDAR_PATH: &str = "/de/kalender";
+
+    // Query parameter of the calendar page that selects the first month shown.
+    const CALENDAR_MONTH_PARAM: &str = "month";
+
+    // Domain in the UID of the screening ICS files.
+    const WEBID_DOMAIN: &str = "www.schauspielhaus.ch";
+
+    // Prefix that all play titles have in common.
+    pub const TITLE_PREFIX: &str = "Schauspielhaus Zürich: ";
+
+    // selectors are parsed once, on first use.
+    fn selectors() -> &'static Selectors {
+        lazy_static! {
+            static ref SELECTORS: Selectors = Selectors {
+                screening: Selector::parse("div.article-event").unwrap(),
+                metainfo: Selector::parse("div.article-content__info").unwrap(),
+                calendar_month: Selector::parse("span.top-calendar__month").unwrap(),
+                calendar_item: Selector::parse("article.calendar-item").unwrap(),
+                calendar_item_text: Selector::parse(
+                    "div.calendar-item__info-allattributes, div.calendar-item__text"
+                )
+                .unwrap(),
+                play_calendar_titles: Selector::parse("a.calendar-item__title").unwrap(),
+                play_title: Selector::parse("h1.article__title").unwrap(),
+                play_description: Selector::parse("div.article-content__text p").unwrap(),
+                play_subtitle: Selector::parse("h2.article__subtitle").unwrap(),
+                play_venue: Selector::parse("div.article-content__info--with-icon").unwrap(),
+                cast: Selector::parse("div.production__actors").unwrap(),
+                credits_role: Selector::parse("div.production__directors > dl > dt").unwrap(),
+                person_link: Selector::parse("a").unwrap(),
+                language_switch: Selector::parse("a.header__lang").unwrap(),
+                ticket_label: Selector::parse(
+                    "span.activity-ticket__label, div.article-event__info"
+                )
+                .unwrap(),
+                screening_date: Selector::parse("div.article-event__date-date").unwrap(),
+                screening_time: Selector::parse("div.article-event__date-time").unwrap(),
+                ticket_button: Selector::parse("a.activity-ticket__button").unwrap(),
+            };
+        }
+        &SELECTORS
+    }
+}
+
+// Selectors are the CSS selectors of the calendar and play pages of the
+// Schauspielhaus website.
+struct Selectors {
     // Select the screening rows on the play page.
-    static ref SCREENING_SELECTOR: Selector = Selector::parse("div.article-event").unwrap();
+    screening: Selector,
     // Select the meta info of the play (duration, subtitles, etc.) on the play page.
-    static ref METAINFO_SELECTOR: Selector = Selector::parse("div.article-content__info").unwrap();
+    metainfo: Selector,
+    // Select the months in the month navigation of the calendar page.
+    calendar_month: Selector,
+    // Select the items (one per screening) on the calendar page.
+    calendar_item: Selector,
+    // Select the attributes ("zu Gast", "Premiere", ...) and the text below
+    // the title of a calendar item, the category of the event is read from them.
+    calendar_item_text: Selector,
     // Select the play titles on the calendar page.
-    static ref PLAY_CALENDAR_TITLES_SELECTOR: Selector = Selector::parse("a.calendar-item__title").unwrap();
+    play_calendar_titles: Selector,
     // Select the play title on the play page.
-    static ref PLAY_TITLE_SELECTOR: Selector = Selector::parse("h1.article__title").unwrap();
+    play_title: Selector,
     // Select the play description on the play page.
-    static ref PLAY_DESCRIPTION_SELECTOR: Selector = Selector::parse("div.article-content__text p").unwrap();
+    play_description: Selector,
     // Select the play subtitle on the play page.
-    static ref PLAY_SUBTITLE_SELECTOR: Selector = Selector::parse("h2.article__subtitle").unwrap();
+    play_subtitle: Selector,
+    // Select the venue of the play on the play page, it is the meta info entry with an icon.
+    play_venue: Selector,
+    // Select the ensemble on the play page, a list of names after "Mit".
+    cast: Selector,
+    // Select the roles of the creative team on the play page, each followed by
+    // a dd with the names. The full list of participants is left out.
+    credits_role: Selector,
+    // Select the links to the person pages in a list of names.
+    person_link: Selector,
+    // Select the link to the page in the other language in the page header.
+    language_switch: Selector,
+    // Select the ticket labels ("Ausverkauft", "Eintritt frei", ...) and
+    // notes ("nur noch wenige Tickets", "Vorstellung entfällt", ...) of a screening row.
+    ticket_label: Selector,
+    // Select the date (23.09.24) and the time of a screening row.
+    screening_date: Selector,
+    screening_time: Selector,
+    // Select the ticket shop link of a screening row.
+    ticket_button: Selector,
 }
 
-pub async fn download_calendar() -> Result<String> {
-    let kalender_url = format!("{}/de/kalender", BASE_URL);
-    let html_content = reqwest::get(&kalender_url)
-        .await
-        .context("loading main calendar page")?
-        .text()
-        .await
-        .context("reading main calendar page")?;
-    Ok(html_content)
-}
+impl TheaterSource for Schauspielhaus {
+    fn slug(&self) -> &'static str {
+        "schauspielhaus"
+    }
 
-#[tokio::test]
-async fn test_download_calendar() {
-    let html_content = download_calendar().await.unwrap();
-    // write content to testdata/calendar.html file
-    let mut file = File::create("src/testdata/calendar.html").unwrap();
-    file.write_all(html_content.as_bytes()).unwrap();
-}
+    fn name(&self) -> &'static str {
+        "Schauspielhaus Zürich"
+    }
 
-fn find_plays(html_content: &str) -> Vec<String> {
-    let fragment = Html::parse_document(html_content);
-    let mut plays: HashSet<String> = HashSet::new();
-    for element in fragment.select(&PLAY_CALENDAR_TITLES_SELECTOR) {
-        let raw_name = element.inner_html();
-        let name = raw_name.trim();
-        let url = match element.value().attr("href") {
-            Some(url) => url.to_string(),
-            None => {
-                error!("No href attribute found for element {}", name);
-                continue;
+    fn base_url(&self) -> &'static str {
+        Self::BASE_URL
+    }
+
+    async fn discover_plays(
+        &self,
+        fetcher: &impl Fetch,
+        horizon_months: u32,
+    ) -> Result<(Vec<CalendarEntry>, Vec<ScrapeError>)> {
+        let (pages, failures) = download_calendar(fetcher, horizon_months).await?;
+
+        // plays that run for several months are listed on several pages
+        let mut seen: HashSet<String> = HashSet::new();
+        let plays = pages
+            .iter()
+            .flat_map(|page| find_plays(page))
+            .filter(|play| seen.insert(play.url.clone()))
+            .collect();
+        Ok((plays, failures))
+    }
+
+    // The language switch links to the English version of the page, it is
+    // only fetched if the plays are translated into English.
+    fn translation_url(&self, play_page_content: &str, language: Language) -> Option<String> {
+        Html::parse_document(play_page_content)
+            .select(&Schauspielhaus::selectors().language_switch)
+            .next()
+            .and_then(|element| element.value().attr("href"))
+            .filter(|href| language == Language::En && href.starts_with("/en/"))
+            .map(|href| href.to_string())
+    }
+
+    fn parse_play(
+        &self,
+        url: &str,
+        play_page_content: &str,
+        translation_page: Option<&str>,
+    ) -> PlayWithScreenings {
+        let mut play = PlayWithScreenings::default();
+
+        {
+            let fragment = Html::parse_document(play_page_content);
+
+            (
+                play.play.name,
+                play.play.description,
+                play.play.description_html,
+            ) = find_title_and_description(&fragment);
+
+            // Get meta info (text that is to the left of the screening times). The
+            // entry with an icon is the venue, which is stored with the screenings.
+            let meta_lines = fragment
+                .select(&Schauspielhaus::selectors().metainfo)
+                .filter(|element| {
+                    !element
+                        .value()
+                        .classes()
+                        .any(|c| c == "article-content__info--with-icon")
+                })
+                .map(|element| {
+                    element
+                        .text()
+                        .collect::<String>()
+                        .split_whitespace()
+                        .collect::<Vec<&str>>()
+                        .join(" ")
+                })
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<String>>();
+            let meta = parse_meta_info(&meta_lines);
+            play.play.duration_minutes = meta.duration_minutes;
+            play.play.intermission = meta.intermission;
+            play.play.language = meta.language;
+            play.play.surtitles = meta.surtitles;
+            play.play.age_recommendation = meta.age_recommendation;
+            play.play.content_warnings = meta.content_warnings;
+            play.play.meta_info = meta.other.join("\n");
+
+            play.credits = find_credits(&fragment);
+
+            // Get Production image
+            let selector = Selector::parse("div.article__hero img").unwrap();
+            for element in fragment.select(&selector) {
+                play.play.image_url = match element.value().attr("data-src") {
+                    Some(url) => url.to_string(),
+                    None => {
+                        error!("No src attribute found for image element {}", url);
+                        continue;
+                    }
+                };
+                break;
+            }
+        }
+
+        play.translations
+            .extend(translation_page.map(find_translation));
+        play
+    }
+
+    async fn parse_screenings(
+        &self,
+        fetcher: &impl Fetch,
+        url: &str,
+        play_page_content: &str,
+    ) -> ParsedScreenings {
+        let mut failures = vec![];
+        let (rows, venue) = {
+            let fragment = Html::parse_document(play_page_content);
+            let mut rows = vec![];
+            for production_row in fragment.select(&Schauspielhaus::selectors().screening) {
+                match find_screening_row(production_row) {
+                    Ok(row) => rows.push(row),
+                    Err(e) => failures.push(ScrapeError::Parse {
+                        path: url.to_string(),
+                        message: e.to_string(),
+                    }),
+                }
             }
+            let venue = fragment
+                .select(&Schauspielhaus::selectors().play_venue)
+                .next()
+                .map(|element| normalize_venue(&element.text().collect::<String>()))
+                .filter(|venue| !venue.is_empty());
+            (rows, venue)
         };
-        plays.insert(url.clone());
+
+        // The ICS files of rows without a time are fetched concurrently, the
+        // screenings keep the order of the rows on the page.
+        let row_count = rows.len() + failures.len();
+        let ics_fallbacks = rows
+            .iter()
+            .filter(|row| row.start.is_none() || webid_from_link(&row.calendar_link).is_none())
+            .count();
+        let mut screenings = vec![];
+        for screening in stream::iter(rows)
+            .map(|row| collect_screening(fetcher, row))
+            .buffered(fetcher.concurrency())
+            .collect::<Vec<Result<Screening, ScrapeError>>>()
+            .await
+        {
+            match screening {
+                // Screenings without a location in their ICS file take place
+                // at the venue of the play.
+                Ok(mut s) => {
+                    if s.venue_name.is_none() {
+                        s.venue_name = venue.clone();
+                    }
+                    screenings.push(s)
+                }
+                Err(e) => failures.push(e),
+            }
+        }
+        ParsedScreenings {
+            screenings,
+            failures,
+            rows: row_count,
+            ics_fallbacks,
+        }
     }
-    plays.into_iter().collect()
 }
 
-#[test]
-fn test_find_plays() {
-    let mut file = File::open("src/testdata/calendar.html").unwrap();
-    let mut html_content = String::new();
-    file.read_to_string(&mut html_content).unwrap();
-    let plays = find_plays(&html_content);
-    // json marshall plays to string with indentation
-    let plays_json = serde_json::to_string_pretty(&plays).unwrap();
-    goldie::assert!(plays_json);
+// Source is one of the theaters that plays are scraped from. TheaterSource is
+// not object safe, the bot keeps a Scraper per source in Source::ALL instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Schauspielhaus(Schauspielhaus),
 }
 
-// get_plays downloads a the plays from the schauspielhaus calendar
-// and returns a map title -> PlayWithScreenings.
-pub async fn get_plays() -> Result<HashMap<String, PlayWithScreenings>> {
-    // base url
-    let html_content = download_calendar().await?;
+impl Source {
+    pub const ALL: [Source; 1] = [Source::Schauspielhaus(Schauspielhaus)];
 
-    let plays = find_plays(&html_content);
-    let mut plays_with_screenings: HashMap<String, PlayWithScreenings> = HashMap::new();
-    for play in plays {
-        let p = match get_play(&play).await {
-            Ok(p) => p,
-            Err(e) => {
-                error!(
-                    "Error while requesting play info {}: {}",
-                    play,
-                    e.to_string()
-                );
-                continue;
-            }
-        };
-        plays_with_screenings.insert(play.clone(), p);
+    // from_slug returns the source with the slug of TheaterSource::slug.
+    pub fn from_slug(slug: &str) -> Option<Source> {
+        Source::ALL.into_iter().find(|s| s.slug() == slug)
     }
-    Ok(plays_with_screenings)
 }
 
-#[tokio::test]
-async fn test_download_play() {
-    let play = &find_plays(&download_calendar().await.unwrap())[1];
-    let play_page_content = reqwest::get(format!("{}{}", BASE_URL, play))
-        .await
-        .unwrap()
-        .text()
-        .await
-        .unwrap();
-    goldie::assert!(play_page_content);
+impl TheaterSource for Source {
+    fn slug(&self) -> &'static str {
+        match self {
+            Source::Schauspielhaus(s) => s.slug(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Source::Schauspielhaus(s) => s.name(),
+        }
+    }
+
+    fn base_url(&self) -> &'static str {
+        match self {
+            Source::Schauspielhaus(s) => s.base_url(),
+        }
+    }
+
+    async fn discover_plays(
+        &self,
+        fetcher: &impl Fetch,
+        horizon_months: u32,
+    ) -> Result<(Vec<CalendarEntry>, Vec<ScrapeError>)> {
+        match self {
+            Source::Schauspielhaus(s) => s.discover_plays(fetcher, horizon_months).await,
+        }
+    }
+
+    fn translation_url(&self, play_page_content: &str, language: Language) -> Option<String> {
+        match self {
+            Source::Schauspielhaus(s) => s.translation_url(play_page_content, language),
+        }
+    }
+
+    fn parse_play(
+        &self,
+        url: &str,
+        play_page_content: &str,
+        translation_page: Option<&str>,
+    ) -> PlayWithScreenings {
+        match self {
+            Source::Schauspielhaus(s) => s.parse_play(url, play_page_content, translation_page),
+        }
+    }
+
+    async fn parse_screenings(
+        &self,
+        fetcher: &impl Fetch,
+        url: &str,
+        play_page_content: &str,
+    ) -> ParsedScreenings {
+        match self {
+            Source::Schauspielhaus(s) => s.parse_screenings(fetcher, url, play_page_content).await,
+        }
+    }
 }
 
 #[tokio::test]
-async fn test_find_play_with_screenings() {
-    // read html from src/testdata/test_download_play.golden
-    let mut file = File::open("src/testdata/test_download_play.golden").unwrap();
-    let mut html_content = String::new();
-    file.read_to_string(&mut html_content).unwrap();
-    let play = find_play_with_screenings("/de/play/der-zerbrochne-krug", &html_content)
-        .await
-        .unwrap();
-    let play_json = serde_json::to_string_pretty(&play).unwrap();
-    goldie::assert!(play_json);
+async fn test_source() {
+    assert_eq!(
+        Source::from_slug("schauspielhaus"),
+        Some(Source::Schauspielhaus(Schauspielhaus))
+    );
+    assert_eq!(Source::from_slug("pfauen"), None);
+    // the source scrapes the same plays as the theater it wraps
+    let scraper =
+        Scraper::builder(Source::ALL[0]).build_with_fetcher(ReplayFetcher::new(REPLAY_DIR));
+    let plays = scraper.plays().await.unwrap();
+    assert_eq!(
+        plays.keys().collect::<Vec<&String>>(),
+        replay_scraper()
+            .plays()
+            .await
+            .unwrap()
+            .keys()
+            .collect::<Vec<&String>>()
+    );
 }
 
-pub async fn find_play_with_screenings(
-    url: &str,
-    play_page_content: &str,
-) -> Result<PlayWithScreenings, Box<dyn Error>> {
-    let mut play = PlayWithScreenings::default();
-    play.play.url = url.to_string();
-
-    let fragment = Html::parse_document(&play_page_content);
+// find_translation returns the title and description of the English page of a
+// play.
+fn find_translation(content: &str) -> PlayTranslation {
+    let (name, description, description_html) =
+        find_title_and_description(&Html::parse_document(content));
+    PlayTranslation {
+        play_id: 0,
+        language: Language::En,
+        name,
+        description,
+        description_html,
+    }
+}
 
-    play.play.name = fragment
-        .select(&PLAY_TITLE_SELECTOR)
+// find_title_and_description returns the title and the description of a play
+// page, as plain text and as the HTML of its paragraphs. The subtitle is the
+// first paragraph of the description.
+fn find_title_and_description(fragment: &Html) -> (String, String, String) {
+    let name = fragment
+        .select(&Schauspielhaus::selectors().play_title)
         .next()
         .map(|element| {
             element
@@ -159,8 +2167,8 @@ pub async fn find_play_with_screenings(
         })
         .unwrap_or("".to_string());
 
-    play.play.description = fragment
-        .select(&PLAY_DESCRIPTION_SELECTOR)
+    let mut description = fragment
+        .select(&Schauspielhaus::selectors().play_description)
         .map(|element| {
             element
                 .text()
@@ -172,8 +2180,19 @@ pub async fn find_play_with_screenings(
         .collect::<Vec<String>>()
         .join("\n");
 
+    let mut description_html = fragment
+        .select(&Schauspielhaus::selectors().play_description)
+        .map(|element| element.html())
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let subtitle_html = fragment
+        .select(&Schauspielhaus::selectors().play_subtitle)
+        .map(|element| format!("<p>{}</p>", element.inner_html().trim()))
+        .collect::<Vec<String>>()
+        .join("\n");
     let subtitle = fragment
-        .select(&PLAY_SUBTITLE_SELECTOR)
+        .select(&Schauspielhaus::selectors().play_subtitle)
         .map(|element| {
             element
                 .text()
@@ -184,56 +2203,300 @@ pub async fn find_play_with_screenings(
         })
         .collect::<Vec<String>>()
         .join("\n");
-    if subtitle != "" {
-        play.play.description = format!("{}\n\n{}", subtitle, play.play.description);
-    }
-
-    // Get meta info (text that is to the left of the screening times)
-    play.play.meta_info = fragment
-        .select(&METAINFO_SELECTOR)
-        .filter_map(|element| {
-            let mut text = element.inner_html();
-            match text.split_once("</span>") {
-                Some((_, t)) => text = t.to_string(),
-                None => (),
-            }
-            text = text.trim().to_string();
-            if text == "" {
-                None
+    if !subtitle.is_empty() {
+        description = format!("{}\n\n{}", subtitle, description);
+        description_html = format!("{}\n{}", subtitle_html, description_html);
+    }
+    (name, description, description_html)
+}
+
+#[test]
+fn test_description_markdown() {
+    // the play pages of the replay fixtures, the description of each is
+    // converted to MarkdownV2 for the pinned message
+    let pages = [
+        "de/kalender/30546/liebe-einfach-ausserirdisch",
+        "de/kalender/31446/poetry-slam-saisoneroeffnung",
+        "de/kalender/32104/der-kirschgarten",
+        "en/kalender/30546/liebe-einfach-ausserirdisch",
+    ];
+    let mut markdown = String::new();
+    for page in pages {
+        let content =
+            std::fs::read_to_string(format!("{}/{}/index.html", REPLAY_DIR, page)).unwrap();
+        let (_, _, description_html) = find_title_and_description(&Html::parse_document(&content));
+        markdown.push_str(&format!(
+            "# {}\n{}\n\n",
+            page,
+            crate::telegram::html_to_markdown(&description_html, Schauspielhaus::BASE_URL)
+        ));
+    }
+    goldie::assert!(markdown);
+}
+
+// MetaInfo holds the information from the meta info entries of a play page.
+#[derive(Default, Debug, PartialEq)]
+struct MetaInfo {
+    duration_minutes: Option<i32>,
+    intermission: Option<bool>,
+    language: Option<String>,
+    surtitles: Option<String>,
+    age_recommendation: Option<String>,
+    content_warnings: Option<String>,
+    // entries that don't match any of the fields above
+    other: Vec<String>,
+}
+
+// parse_meta_info sorts the meta info entries of a play page into the fields
+// of MetaInfo. An entry may contain several comma separated pieces of
+// information, e.g. "1 Std. 25 Min., keine Pause" or "auf Schweizerdeutsch,
+// mit Deutschen Übertiteln".
+fn parse_meta_info(lines: &[String]) -> MetaInfo {
+    lazy_static! {
+        static ref AGE: Regex =
+            Regex::new(r"(?i)(\bab \d+ (jahren|jahre|j\.)|altersempfehlung|\b\d+\+)").unwrap();
+        static ref WARNING: Regex =
+            Regex::new(r"(?i)^(inhaltshinweis|inhaltswarnung|triggerwarnung|content warning|hinweis:)")
+                .unwrap();
+        static ref LANGUAGE: Regex = Regex::new(
+            r"(?i)(sprache|^((auf|in) .*)?((hoch|schweizer)?deutsch|englisch|französisch|italienisch|spanisch|rätoromanisch)$)"
+        )
+        .unwrap();
+    }
+    let mut meta = MetaInfo::default();
+    let mut language: Vec<&str> = Vec::new();
+    let mut warnings: Vec<&str> = Vec::new();
+    for line in lines {
+        if WARNING.is_match(line) {
+            warnings.push(line);
+            continue;
+        }
+        let mut other: Vec<&str> = Vec::new();
+        for part in line.split(", ").map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let lower = part.to_lowercase();
+            if lower.contains("pause") {
+                meta.intermission = Some(!(lower.contains("keine") || lower.contains("ohne")));
+            } else if lower.contains("übertitel") || lower.contains("surtitle") {
+                meta.surtitles = Some(part.to_string());
+            } else if let (Some(duration), None) = (parse_duration(part), meta.duration_minutes) {
+                meta.duration_minutes = i32::try_from(duration.whole_minutes()).ok();
+            } else if AGE.is_match(part) {
+                meta.age_recommendation = Some(part.to_string());
+            } else if LANGUAGE.is_match(part) {
+                language.push(part);
             } else {
-                Some(text)
+                other.push(part);
             }
-        })
-        .collect::<Vec<String>>()
-        .join("\n");
+        }
+        if !other.is_empty() {
+            meta.other.push(other.join(", "));
+        }
+    }
+    if !language.is_empty() {
+        meta.language = Some(language.join(", "));
+    }
+    if !warnings.is_empty() {
+        meta.content_warnings = Some(warnings.join("\n"));
+    }
+    meta
+}
 
-    for production_row in fragment.select(&SCREENING_SELECTOR) {
-        match collect_screening(production_row).await {
-            Ok(s) => play.screenings.push(s),
-            Err(e) => {
-                error!("Error collecting screening: {}", e.to_string());
-            }
+#[test]
+fn test_parse_meta_info() {
+    let lines = [
+        "1 Std. 25 Min., keine Pause",
+        "Zürich-Premiere: 21. September 2024",
+        "auf Schweizerdeutsch, Hochdeutsch, Rätoromanisch, mit Deutschen Übertiteln",
+        "Empfohlen ab 14 Jahren",
+        "Inhaltshinweis: Gewalt, Suizid",
+    ]
+    .map(String::from);
+    assert_eq!(
+        parse_meta_info(&lines),
+        MetaInfo {
+            duration_minutes: Some(85),
+            intermission: Some(false),
+            language: Some("auf Schweizerdeutsch, Hochdeutsch, Rätoromanisch".to_string()),
+            surtitles: Some("mit Deutschen Übertiteln".to_string()),
+            age_recommendation: Some("Empfohlen ab 14 Jahren".to_string()),
+            content_warnings: Some("Inhaltshinweis: Gewalt, Suizid".to_string()),
+            other: vec!["Zürich-Premiere: 21. September 2024".to_string()],
         }
+    );
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("ca. 2 Std. 30 Min., mit Pause", Some(150), Some(true))]
+#[case("1 Std. 10 Min.", Some(70), None)]
+#[case("Dauer: 90 Minuten, ohne Pause", Some(90), Some(false))]
+#[case("Eine Pause", None, Some(true))]
+fn test_parse_meta_info_duration(
+    #[case] line: &str,
+    #[case] minutes: Option<i32>,
+    #[case] intermission: Option<bool>,
+) {
+    let meta = parse_meta_info(&[line.to_string()]);
+    assert_eq!(meta.duration_minutes, minutes);
+    assert_eq!(meta.intermission, intermission);
+    assert!(meta.other.is_empty());
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("Englische Übertitel", None, Some("Englische Übertitel"))]
+#[case("Deutsch", Some("Deutsch"), None)]
+#[case("in englischer Sprache", Some("in englischer Sprache"), None)]
+#[case(
+    "Tanz/Theater/Zirkus (ohne Sprache)",
+    Some("Tanz/Theater/Zirkus (ohne Sprache)"),
+    None
+)]
+fn test_parse_meta_info_language(
+    #[case] line: &str,
+    #[case] language: Option<&str>,
+    #[case] surtitles: Option<&str>,
+) {
+    let meta = parse_meta_info(&[line.to_string()]);
+    assert_eq!(meta.language.as_deref(), language);
+    assert_eq!(meta.surtitles.as_deref(), surtitles);
+}
+
+// parse_duration extracts the duration of a play from its meta info, e.g.
+// "1 Std. 25 Min., keine Pause" or "ca. 90 Minuten".
+fn parse_duration(meta_info: &str) -> Option<time::Duration> {
+    lazy_static! {
+        static ref HOURS: Regex = Regex::new(r"(\d+)\s*(Std\.|Stunden?)").unwrap();
+        static ref MINUTES: Regex = Regex::new(r"(\d+)\s*(Min\.|Minuten)").unwrap();
     }
+    let capture = |re: &Regex| -> Option<i64> { re.captures(meta_info)?[1].parse().ok() };
+    match (capture(&HOURS), capture(&MINUTES)) {
+        (None, None) => None,
+        (h, m) => Some(time::Duration::minutes(
+            h.unwrap_or(0) * 60 + m.unwrap_or(0),
+        )),
+    }
+}
 
-    // Get Production image
-    let selector = Selector::parse("div.article__hero img").unwrap();
-    for element in fragment.select(&selector) {
-        play.play.image_url = match element.value().attr("data-src") {
-            Some(url) => url.to_string(),
-            None => {
-                error!("No src attribute found for image element {}", url);
-                continue;
-            }
-        };
-        break;
+#[cfg(test)]
+#[rstest]
+#[case("Pfauen\n1 Std. 25 Min., keine Pause", Some(85))]
+#[case("2 Std. 30 Min., inkl. Pause", Some(150))]
+#[case("ca. 1 Stunde", Some(60))]
+#[case("ca. 90 Minuten", Some(90))]
+#[case("45 Min.", Some(45))]
+#[case("Pfauen\nZürich-Premiere: 21. September 2024", None)]
+fn test_parse_duration(#[case] meta_info: &str, #[case] minutes: Option<i64>) {
+    assert_eq!(
+        parse_duration(meta_info),
+        minutes.map(time::Duration::minutes)
+    );
+}
+
+// find_credits returns the ensemble followed by the creative team of a play
+// page, one credit per person and role.
+fn find_credits(fragment: &Html) -> Vec<Credit> {
+    let mut credits = Vec::new();
+    for cast in fragment.select(&Schauspielhaus::selectors().cast) {
+        credits.extend(find_persons(cast, CAST_ROLE));
+    }
+    for role in fragment.select(&Schauspielhaus::selectors().credits_role) {
+        let role_name = clean_name(&role.text().collect::<String>());
+        let names = role
+            .next_siblings()
+            .filter_map(ElementRef::wrap)
+            .next()
+            .filter(|element| element.value().name() == "dd");
+        if let (false, Some(names)) = (role_name.is_empty(), names) {
+            credits.extend(find_persons(names, &role_name));
+        }
     }
+    credits
+}
+
+// find_persons splits a list of names like "Mit A / B / C" into credits for
+// `role`, taking the urls of the person pages from the links.
+fn find_persons(element: ElementRef<'_>, role: &str) -> Vec<Credit> {
+    let links = element
+        .select(&Schauspielhaus::selectors().person_link)
+        .filter_map(|link| {
+            let name = clean_name(&link.text().collect::<String>());
+            // drop the ?origin=… query, it only refers back to the play
+            let url = link.value().attr("href")?.split('?').next()?.to_string();
+            Some((name, url))
+        })
+        .collect::<Vec<(String, String)>>();
+    let text = clean_name(&element.text().collect::<String>());
+    let text = match text.strip_prefix(role) {
+        Some(t) if role == CAST_ROLE => t,
+        _ => &text,
+    };
+    text.split(" / ")
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Credit {
+            role: role.to_string(),
+            name: name.to_string(),
+            url: links
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, url)| url.clone()),
+        })
+        .collect()
+}
+
+// clean_name collapses whitespace including the non-breaking spaces that are
+// used between first and last names.
+fn clean_name(name: &str) -> String {
+    name.replace('\u{a0}', " ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+#[test]
+fn test_find_credits() {
+    let mut html_content = String::new();
+    File::open("testdata/play_curl.html")
+        .unwrap()
+        .read_to_string(&mut html_content)
+        .unwrap();
+    let credits = find_credits(&Html::parse_document(&html_content));
+    let cast = credits.iter().filter(|c| c.role == CAST_ROLE).count();
+    assert_eq!(cast, 30);
+    assert_eq!(
+        credits[0],
+        Credit {
+            role: "Mit".to_string(),
+            name: "Gizem Baruk".to_string(),
+            url: Some("/de/personen/27376/gizem-baruk".to_string()),
+        }
+    );
+    assert!(credits.contains(&Credit {
+        role: "Bühnen- und Kostümbild".to_string(),
+        name: "Cleo Niemeyer-Nasser".to_string(),
+        url: None,
+    }));
+    // the full list of participants is not included
+    assert!(!credits.iter().any(|c| c.role == "Inspizienz"));
+    assert_eq!(
+        crate::models::credits_summary(&credits).unwrap(),
+        "Regie: Suna Gürler / Mit: Gizem Baruk, Yanike Mica Becklas, Helene Bott, \
+         Onur Can, Timon Däster, Xhenisa Demiri u.a."
+    );
+}
 
-    // Find Location
-    Ok(play)
+// ScreeningRow is what a screening row of the play page tells about the
+// screening. The start is None when the row shows no readable date and time,
+// then it is read from the ICS file.
+struct ScreeningRow {
+    calendar_link: String,
+    start: Option<OffsetDateTime>,
+    ticket_url: String,
+    ticket_label: String,
 }
 
-async fn collect_screening(production_row: ElementRef<'_>) -> Result<Screening> {
+fn find_screening_row(production_row: ElementRef<'_>) -> Result<ScreeningRow> {
     // Search for `a.calendar-icon` in the production row
     let selector = Selector::parse("div.activity-ticket__calendar a").unwrap();
     // Extract the calendar event link
@@ -246,101 +2509,641 @@ async fn collect_screening(production_row: ElementRef<'_>) -> Result<Screening>
         .context("error finding href attribute for element")?
         .to_string();
 
-    let mut ticket_url = "".to_string();
-    // look for span.activity-ticket__label with text "Ausverkauft"
-    let sold_out_selector = Selector::parse("span.activity-ticket__label").unwrap();
-    let sold_out = production_row
-        .select(&sold_out_selector)
+    let ticket_url = production_row
+        .select(&Schauspielhaus::selectors().ticket_button)
         .next()
-        .map(|element| element.inner_html().contains("Ausverkauft"));
-    if sold_out == Some(true) {
-        ticket_url = "Ausverkauft".to_string();
-    } else {
-        // look for the href of a.activity-ticket__button
-        let ticket_selector = Selector::parse("a.activity-ticket__button").unwrap();
-        let url = production_row
-            .select(&ticket_selector)
-            .next()
-            .map(|element| element.value().attr("href"))
-            .flatten();
-        if let Some(u) = url {
-            ticket_url = u.to_string();
-        }
+        .and_then(|element| element.value().attr("href"))
+        .unwrap_or_default()
+        .to_string();
+    let ticket_label = production_row
+        .select(&Schauspielhaus::selectors().ticket_label)
+        .flat_map(|element| element.text())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    let date = production_row
+        .select(&Schauspielhaus::selectors().screening_date)
+        .next()
+        .map(|element| element.text().collect::<String>());
+    // The label has the time as 20:00, the text as 20.00.
+    let time = production_row
+        .select(&Schauspielhaus::selectors().screening_time)
+        .next()
+        .map(|element| {
+            element
+                .value()
+                .attr("aria-label")
+                .map(str::to_string)
+                .unwrap_or_else(|| element.text().collect::<String>())
+        });
+    let start = match (date, time) {
+        (Some(date), Some(time)) => parse_row_time(&date, &time),
+        _ => None,
+    };
+    Ok(ScreeningRow {
+        calendar_link,
+        start,
+        ticket_url,
+        ticket_label,
+    })
+}
+
+// collect_screening builds the screening of a row. The id is derived from the
+// calendar link and the start from the row, so the ICS file is only
+// downloaded for rows that can't be parsed.
+async fn collect_screening(
+    fetcher: &impl Fetch,
+    row: ScreeningRow,
+) -> Result<Screening, ScrapeError> {
+    match (webid_from_link(&row.calendar_link), row.start) {
+        (Some(webid), Some(start)) => Ok(new_screening(row, webid, start, None, None)),
+        _ => collect_screening_from_ics(fetcher, row).await,
+    }
+}
+
+// new_screening builds a screening from its row and the id, time and venue
+// that were read from the row or the ICS file.
+fn new_screening(
+    row: ScreeningRow,
+    webid: String,
+    start: OffsetDateTime,
+    end: Option<OffsetDateTime>,
+    venue_name: Option<String>,
+) -> Screening {
+    let (ticket_status, presale_date) = parse_ticket_status(&row.ticket_label, start);
+    Screening {
+        id: 0,
+        play_id: 0,
+        url: row.calendar_link,
+        venue_name,
+        webid,
+        start_time: start,
+        ticket_url: row.ticket_url,
+        venue_id: None,
+        // an end before the start is a mistake in the ICS file
+        end_time: end.filter(|e| *e > start),
+        ticket_status,
+        presale_date,
+        tags: parse_screening_tags(&row.ticket_label),
+        removed_at: None,
     }
+}
 
+async fn collect_screening_from_ics(
+    fetcher: &impl Fetch,
+    row: ScreeningRow,
+) -> Result<Screening, ScrapeError> {
     // Download ics file at the calendar link and parse the contents to extract
     // Description, start and end date.
-    let buf = reqwest::get(format!("{}{}", BASE_URL, calendar_link))
-        .await?
-        .bytes()
-        .await?;
-    let reader = ical::PropertyParser::from_reader(buf.as_ref());
+    let buf = fetcher.fetch(&row.calendar_link).await?;
+    let reader = ical::PropertyParser::from_reader(buf.as_slice());
     let mut id: Option<String> = None;
     let mut start: Option<OffsetDateTime> = None;
+    let mut end: Option<OffsetDateTime> = None;
+    let mut venue_name: Option<String> = None;
 
     for l in reader {
-        let line = l?;
-        match (line.name.as_str(), line.value) {
-            ("UID", Some(i)) => id = Some(i),
-            ("DTSTART", Some(d)) => start = parse_time(d),
+        let line = l.map_err(|e| ScrapeError::Parse {
+            path: row.calendar_link.clone(),
+            message: e.to_string(),
+        })?;
+        let tzid = property_param(&line, "TZID");
+        match (line.name.as_str(), &line.value) {
+            ("UID", Some(i)) => id = Some(i.clone()),
+            ("DTSTART", Some(d)) => start = parse_time(d, tzid),
+            ("DTEND", Some(d)) => end = parse_time(d, tzid),
+            ("LOCATION", Some(l)) => {
+                venue_name = Some(normalize_venue(l)).filter(|v| !v.is_empty())
+            }
             (_, _) => continue,
         }
     }
-    let screening: Screening;
     match (id, start) {
-        (Some(i), Some(s)) => {
-            screening = Screening {
-                id: 0,
-                play_id: 0,
-                url: calendar_link,
-                location: "".to_string(),
-                webid: i,
-                start_time: s,
-                ticket_url: ticket_url,
+        (Some(i), Some(s)) => Ok(new_screening(row, i, s, end, venue_name)),
+        (i, s) => Err(ScrapeError::Parse {
+            message: format!("missing id {:?} or start {:?}", i, s),
+            path: row.calendar_link,
+        }),
+    }
+}
+
+// webid_from_link derives the id of a screening from its calendar link, e.g.
+// `/de/kalender/30546/play/31436.ics` -> `event_31436@www.schauspielhaus.ch`.
+// It is the UID of the ICS file, so screenings keep their id either way.
+fn webid_from_link(calendar_link: &str) -> Option<String> {
+    let event = calendar_link
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()?
+        .strip_suffix(".ics")?;
+    if event.is_empty() || !event.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("event_{}@{}", event, Schauspielhaus::WEBID_DOMAIN))
+}
+
+#[cfg(test)]
+#[rstest]
+#[case(
+    "/de/kalender/30546/liebe-einfach-ausserirdisch/31436.ics",
+    Some("event_31436@www.schauspielhaus.ch")
+)]
+#[case("/de/kalender/30546/liebe-einfach-ausserirdisch/abc.ics", None)]
+#[case("/de/kalender/30546/liebe-einfach-ausserirdisch", None)]
+#[case("", None)]
+fn test_webid_from_link(#[case] link: &str, #[case] webid: Option<&str>) {
+    assert_eq!(webid_from_link(link).as_deref(), webid);
+}
+
+// parse_row_time parses the date (`23.09.24`) and time (`20:00` or `20.00`)
+// of a screening row as Zurich time.
+fn parse_row_time(date: &str, time: &str) -> Option<OffsetDateTime> {
+    let value = format!("{} {}", date.trim(), time.trim().replace('.', ":"));
+    let naive = NaiveDateTime::parse_from_str(&value, "%d.%m.%y %H:%M").ok()?;
+    local_time(naive, Zurich)
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("23.09.24", "20:00", Some(datetime!(2024-09-23 20:00 +02:00)))]
+#[case("\n  02.11.24\n ", " 19.30 ", Some(datetime!(2024-11-02 19:30 +01:00)))]
+#[case("02.11.2024", "19:30", None)]
+#[case("Sa", "19:30", None)]
+#[case("02.11.24", "", None)]
+fn test_parse_row_time(
+    #[case] date: &str,
+    #[case] time: &str,
+    #[case] expected: Option<OffsetDateTime>,
+) {
+    assert_eq!(parse_row_time(date, time), expected);
+}
+
+#[tokio::test]
+async fn test_collect_screening_falls_back_to_ics() {
+    let fetcher = ReplayFetcher::new(REPLAY_DIR);
+    let row = ScreeningRow {
+        calendar_link: "/de/kalender/30546/liebe-einfach-ausserirdisch/31436.ics".to_string(),
+        start: None,
+        ticket_url: String::new(),
+        ticket_label: String::new(),
+    };
+    let screening = collect_screening(&fetcher, row).await.unwrap();
+    assert_eq!(screening.webid, "event_31436@www.schauspielhaus.ch");
+    assert_eq!(screening.start_time, datetime!(2024-09-23 20:00 +02:00));
+    assert_eq!(screening.end_time, Some(datetime!(2024-09-23 21:25 +02:00)));
+    assert_eq!(screening.venue(), Some("Pfauen"));
+}
+
+// parse_ticket_status derives the ticket availability of a screening from the
+// labels and notes of its row. Rows without any known label are available.
+// Presale dates without a year are assumed to be in the year before `start`.
+fn parse_ticket_status(label: &str, start: OffsetDateTime) -> (TicketStatus, Option<Date>) {
+    let label = label.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| label.contains(w));
+    if has(&["entfällt", "abgesagt", "vorstellungsausfall", "cancelled"]) {
+        (TicketStatus::Cancelled, None)
+    } else if has(&["ausverkauft", "sold out"]) {
+        (TicketStatus::SoldOut, None)
+    } else if has(&["warteliste", "waiting list"]) {
+        (TicketStatus::Waitlist, None)
+    } else if has(&["eintritt frei", "freier eintritt", "free entry"]) {
+        (TicketStatus::FreeEntry, None)
+    } else if has(&["wenige tickets", "restkarten", "few tickets"]) {
+        (TicketStatus::FewLeft, None)
+    } else if has(&["vorverkauf ab", "vorverkaufsstart", "vorverkauf startet"]) {
+        (TicketStatus::Presale, parse_presale_date(&label, start))
+    } else {
+        (TicketStatus::Available, None)
+    }
+}
+
+// parse_screening_tags finds the labels of special screenings in the labels
+// and notes of a screening row.
+fn parse_screening_tags(label: &str) -> ScreeningTags {
+    ScreeningTags(
+        SCREENING_TAGS
+            .iter()
+            .filter(|(_, regex)| regex.is_match(label))
+            .map(|(tag, _)| *tag)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("", vec![])]
+#[case("Zürich-Premiere", vec![ScreeningTag::Premiere])]
+#[case("Premierenverschiebung", vec![])]
+#[case("Uraufführung mit anschliessendem Publikumsgespräch", vec![ScreeningTag::Premiere, ScreeningTag::Talk])]
+#[case("Dernière", vec![ScreeningTag::LastShow])]
+#[case("Letzte Vorstellung", vec![ScreeningTag::LastShow])]
+#[case("mit Einführung", vec![ScreeningTag::Introduction])]
+#[case("Ausverkauft mit englischen Übertiteln", vec![ScreeningTag::Surtitles])]
+#[case("Relaxed Performance", vec![ScreeningTag::Relaxed])]
+fn test_parse_screening_tags(#[case] label: &str, #[case] tags: Vec<ScreeningTag>) {
+    assert_eq!(parse_screening_tags(label), ScreeningTags(tags));
+}
+
+fn parse_presale_date(label: &str, start: OffsetDateTime) -> Option<Date> {
+    let captures = PRESALE_DATE.captures(label)?;
+    let day: u8 = captures[1].parse().ok()?;
+    let month = Month::try_from(captures[2].parse::<u8>().ok()?).ok()?;
+    match captures.get(3).map(|y| y.as_str().parse::<i32>()) {
+        Some(Ok(y)) if y < 100 => Date::from_calendar_date(2000 + y, month, day).ok(),
+        Some(Ok(y)) => Date::from_calendar_date(y, month, day).ok(),
+        Some(Err(_)) => None,
+        None => {
+            let date = Date::from_calendar_date(start.year(), month, day).ok()?;
+            if date > start.date() {
+                Date::from_calendar_date(start.year() - 1, month, day).ok()
+            } else {
+                Some(date)
             }
         }
-        (i, s) => {
-            return Err(anyhow!(
-                "error filling screening link: {}, id {:?}, start {:?}",
-                calendar_link,
-                i,
-                s,
-            ));
+    }
+}
+
+#[cfg(test)]
+#[rstest]
+#[case("", TicketStatus::Available, None)]
+#[case("bereits im Vorverkauf", TicketStatus::Available, None)]
+#[case("Ausverkauft", TicketStatus::SoldOut, None)]
+#[case(
+    "Ausverkauft evtl. Restkarten an der Abendkasse",
+    TicketStatus::SoldOut,
+    None
+)]
+#[case("nur noch wenige Tickets", TicketStatus::FewLeft, None)]
+#[case("Warteliste", TicketStatus::Waitlist, None)]
+#[case("Eintritt frei", TicketStatus::FreeEntry, None)]
+#[case("Vorstellung entfällt", TicketStatus::Cancelled, None)]
+#[case(
+    "Vorverkauf ab 1.10.2024",
+    TicketStatus::Presale,
+    Some(time::macros::date!(2024 - 10 - 01))
+)]
+#[case(
+    "Vorverkaufsstart: 15.08.",
+    TicketStatus::Presale,
+    Some(time::macros::date!(2024 - 08 - 15))
+)]
+#[case(
+    "Vorverkauf ab 15.12.",
+    TicketStatus::Presale,
+    Some(time::macros::date!(2023 - 12 - 15))
+)]
+#[case("Vorverkauf ab Oktober", TicketStatus::Presale, None)]
+fn test_parse_ticket_status(
+    #[case] label: &str,
+    #[case] status: TicketStatus,
+    #[case] presale_date: Option<Date>,
+) {
+    assert_eq!(
+        parse_ticket_status(label, datetime!(2024-09-23 20:00 +02:00)),
+        (status, presale_date)
+    );
+}
+
+// scrape_play_if_changed is like scrape_play for the play of a calendar
+// entry, but fetches the page and its translation first and returns None
+// without parsing them if their content hash is `known_hash`.
+async fn scrape_play_if_changed(
+    source: &impl TheaterSource,
+    fetcher: &impl Fetch,
+    entry: &CalendarEntry,
+    known_hash: Option<&str>,
+    language: Language,
+) -> Result<Option<FetchedPlay>, ScrapeError> {
+    let play_page_content = fetcher.fetch_text(&entry.url).await?;
+    let translation_page = fetch_translation(source, fetcher, &play_page_content, language).await;
+    if let Ok(page) = &translation_page {
+        let hash = content_hash(&play_page_content, page.as_deref(), entry.category);
+        if known_hash == Some(hash.as_str()) {
+            return Ok(None);
         }
     }
-    Ok(screening)
+    Ok(Some(
+        parse_fetched_play(
+            source,
+            fetcher,
+            &entry.url,
+            entry.category,
+            &play_page_content,
+            translation_page,
+        )
+        .await,
+    ))
+}
+
+// validate_play checks that a parsed play has what every play page has, when
+// the selectors no longer match after a redesign of the website the play would
+// be stored with an empty title or without its screenings.
+pub fn validate_play(url: &str, play: &PlayWithScreenings) -> Result<(), ScrapeError> {
+    let message = if play.play.name.trim().is_empty() {
+        "play has no title"
+    } else if play.screenings.is_empty() {
+        "play has no screenings"
+    } else {
+        return Ok(());
+    };
+    Err(ScrapeError::Parse {
+        path: url.to_string(),
+        message: message.to_string(),
+    })
+}
+
+// content_hash returns a hash of a play page, the page of its translation and
+// its category in the calendar that is stable across runs and changes with
+// PARSER_VERSION.
+pub fn content_hash(
+    content: &str,
+    translation_page: Option<&str>,
+    category: EventCategory,
+) -> String {
+    let mut hasher = fnv::FnvHasher::default();
+    hasher.write_u32(PARSER_VERSION);
+    hasher.write(category.as_str().as_bytes());
+    hasher.write_u8(0);
+    hasher.write(content.as_bytes());
+    if let Some(page) = translation_page {
+        hasher.write_u8(0);
+        hasher.write(page.as_bytes());
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+// MirroredImage are the files of a play image that was downloaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirroredImage {
+    // The image as served by the website.
+    pub original: PathBuf,
+    // A JPEG that fits into THUMBNAIL_SIZE, for posting it.
+    pub thumbnail: PathBuf,
+}
+
+// mirrored_image returns where the image at `image_url` is stored in `dir`,
+// the files are named after a hash of the url.
+pub fn mirrored_image(dir: &Path, image_url: &str) -> MirroredImage {
+    let mut hasher = fnv::FnvHasher::default();
+    hasher.write(image_url.as_bytes());
+    let stem = format!("{:016x}", hasher.finish());
+    let extension = Path::new(image_url.split('?').next().unwrap_or_default())
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("img");
+    MirroredImage {
+        original: dir.join(format!("{}.{}", stem, extension)),
+        thumbnail: dir.join(format!("{}.thumb.jpg", stem)),
+    }
+}
+
+// mirror_image downloads the image at `image_url` into `dir` and writes its
+// thumbnail. Images that were mirrored before are not downloaded again.
+pub async fn mirror_image(
+    fetcher: &impl Fetch,
+    dir: &Path,
+    image_url: &str,
+) -> Result<MirroredImage, ScrapeError> {
+    let image = mirrored_image(dir, image_url);
+    if tokio::fs::try_exists(&image.thumbnail)
+        .await
+        .unwrap_or(false)
+    {
+        return Ok(image);
+    }
+    let body = fetcher.fetch(image_url).await?;
+    let io_error = |source: anyhow::Error| ScrapeError::Io {
+        path: image_url.to_string(),
+        source,
+    };
+    let parse_error = |message: String| ScrapeError::Parse {
+        path: image_url.to_string(),
+        message,
+    };
+    async {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&image.original, &body).await
+    }
+    .await
+    .with_context(|| format!("Error writing {}", image.original.display()))
+    .map_err(io_error)?;
+    // decoding and encoding the image would block the runtime
+    let thumbnail = tokio::task::spawn_blocking(move || thumbnail(&body))
+        .await
+        .map_err(|e| parse_error(e.to_string()))?
+        .map_err(|e| parse_error(e.to_string()))?;
+    tokio::fs::write(&image.thumbnail, thumbnail)
+        .await
+        .with_context(|| format!("Error writing {}", image.thumbnail.display()))
+        .map_err(io_error)?;
+    Ok(image)
+}
+
+// thumbnail scales an image down to fit into THUMBNAIL_SIZE and encodes it as
+// JPEG. Smaller images keep their size.
+fn thumbnail(body: &[u8]) -> Result<Vec<u8>, image::ImageError> {
+    let mut image = image::load_from_memory(body)?;
+    if image.width() > THUMBNAIL_SIZE || image.height() > THUMBNAIL_SIZE {
+        image = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+    }
+    let mut thumbnail = vec![];
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut thumbnail, THUMBNAIL_QUALITY)
+        .encode_image(&image.to_rgb8())?;
+    Ok(thumbnail)
+}
+
+// mirror_images mirrors the images of the plays into `dir` and returns the
+// images that failed.
+async fn mirror_images<'a>(
+    fetcher: &impl Fetch,
+    dir: &Path,
+    image_urls: impl IntoIterator<Item = &'a str>,
+) -> Vec<ScrapeError> {
+    stream::iter(image_urls.into_iter().filter(|url| !url.is_empty()))
+        .map(|url| mirror_image(fetcher, dir, url))
+        .buffer_unordered(fetcher.concurrency())
+        .filter_map(|result| async move { result.err() })
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn test_mirror_image() {
+    let dir = std::env::temp_dir().join(format!("schauspielhaus-images-{}", std::process::id()));
+    let image_url = "/images/Zueri_Meisterschaften_68_2.jpg?w=1600";
+    let mut png = vec![];
+    image::DynamicImage::new_rgb8(1600, 900)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    let site = dir.join("site");
+    let file = fixture_path(&site, image_url);
+    std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+    std::fs::write(&file, &png).unwrap();
+
+    let mirrored = dir.join("mirrored");
+    let image = mirror_image(&ReplayFetcher::new(&site), &mirrored, image_url)
+        .await
+        .unwrap();
+    assert_eq!(image, mirrored_image(&mirrored, image_url));
+    assert_eq!(image.original.extension().unwrap(), "jpg");
+    let original = std::fs::read(&image.original).unwrap();
+    let thumbnail = image::open(&image.thumbnail).unwrap();
+
+    // mirrored images are not downloaded again
+    std::fs::remove_file(&file).unwrap();
+    let again = mirror_image(&ReplayFetcher::new(&site), &mirrored, image_url).await;
+    let failures = mirror_images(
+        &ReplayFetcher::new(&site),
+        &mirrored,
+        ["", image_url, "/images/missing.jpg"],
+    )
+    .await;
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(original, png);
+    assert_eq!(
+        (thumbnail.width(), thumbnail.height()),
+        (THUMBNAIL_SIZE, 720)
+    );
+    assert_eq!(again.unwrap(), image);
+    assert_eq!(
+        failures.iter().map(|e| e.path()).collect::<Vec<&str>>(),
+        vec!["/images/missing.jpg"]
+    );
 }
 
-pub async fn get_play<'a>(url: &str) -> Result<PlayWithScreenings, Box<dyn Error>> {
-    let play_page_content = reqwest::get(format!("{}{}", BASE_URL, url))
-        .await?
-        .text()
-        .await?;
-    find_play_with_screenings(url, &play_page_content).await
+// property_param returns the first value of the parameter `name` of an ICS
+// property, e.g. `Europe/Zurich` for `DTSTART;TZID=Europe/Zurich:...`.
+fn property_param<'a>(property: &'a Property, name: &str) -> Option<&'a str> {
+    property
+        .params
+        .as_ref()?
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .and_then(|(_, values)| values.first())
+        .map(|v| v.as_str())
 }
 
-fn parse_time(d: String) -> Option<OffsetDateTime> {
-    let t = match NaiveDateTime::parse_from_str(d.as_str(), "%Y%m%dT%H%M%S") {
+// parse_time parses an ICS date-time value. Values with a `Z` suffix are in
+// UTC, otherwise the time is local to `tzid`. Floating times (without TZID)
+// are interpreted as Zurich time, as that is where all screenings take place.
+fn parse_time(d: &str, tzid: Option<&str>) -> Option<OffsetDateTime> {
+    let (value, utc) = match d.strip_suffix('Z') {
+        Some(v) => (v, true),
+        None => (d, false),
+    };
+    let naive = match NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
         Ok(t) => t,
         Err(e) => {
-            error!("error parsing time: {}", e.to_string());
+            error!("error parsing time {}: {}", d, e);
             return None;
         }
     };
-    let datetime = OffsetDateTime::from_unix_timestamp(t.timestamp()).unwrap();
-    Some(datetime.replace_offset(UtcOffset::from_whole_seconds(7200).unwrap()))
+    if utc {
+        return OffsetDateTime::from_unix_timestamp(naive.and_utc().timestamp()).ok();
+    }
+    let tz: Tz = match tzid {
+        None => Zurich,
+        Some(name) => match name.parse() {
+            Ok(tz) => tz,
+            Err(e) => {
+                error!("unknown TZID {}, assuming Europe/Zurich: {}", name, e);
+                Zurich
+            }
+        },
+    };
+    local_time(naive, tz)
+}
+
+// local_time converts a wall clock time in `tz` to an OffsetDateTime that
+// keeps the local offset.
+fn local_time(naive: NaiveDateTime, tz: Tz) -> Option<OffsetDateTime> {
+    let local = match tz.from_local_datetime(&naive) {
+        LocalResult::Single(t) => t,
+        // The clocks are turned back and the time occurs twice, take the
+        // first occurrence (summer time).
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        // The clocks are turned forward and the time doesn't exist, move it
+        // past the gap.
+        LocalResult::None => tz
+            .from_local_datetime(&(naive + chrono::Duration::hours(1)))
+            .earliest()?,
+    };
+    let offset = UtcOffset::from_whole_seconds(local.offset().fix().local_minus_utc()).ok()?;
+    OffsetDateTime::from_unix_timestamp(local.timestamp())
+        .ok()
+        .map(|t| t.to_offset(offset))
+}
+
+#[cfg(test)]
+#[rstest]
+// summer time (CEST, UTC+2)
+#[case("20240923T200000", Some("Europe/Zurich"), datetime!(2024-09-23 18:00:00 UTC))]
+// winter time (CET, UTC+1)
+#[case("20241102T200000", Some("Europe/Zurich"), datetime!(2024-11-02 19:00:00 UTC))]
+#[case("20250115T193000", None, datetime!(2025-01-15 18:30:00 UTC))]
+#[case("20250715T193000", None, datetime!(2025-07-15 17:30:00 UTC))]
+#[case("20241102T190000Z", None, datetime!(2024-11-02 19:00:00 UTC))]
+#[case("20241102T190000Z", Some("Europe/Zurich"), datetime!(2024-11-02 19:00:00 UTC))]
+#[case("20241102T200000", Some("Europe/London"), datetime!(2024-11-02 20:00:00 UTC))]
+#[case("20241102T200000", Some("Mars/Olympus_Mons"), datetime!(2024-11-02 19:00:00 UTC))]
+// last hour of summer time and first hour of winter time around the switch
+// on 2024-10-27 at 03:00 CEST -> 02:00 CET
+#[case("20241027T015959", None, datetime!(2024-10-26 23:59:59 UTC))]
+#[case("20241027T030000", None, datetime!(2024-10-27 02:00:00 UTC))]
+// 02:30 happens twice, the first occurrence is in summer time
+#[case("20241027T023000", None, datetime!(2024-10-27 00:30:00 UTC))]
+// switch to summer time on 2025-03-30 at 02:00 CET -> 03:00 CEST
+#[case("20250330T015959", None, datetime!(2025-03-30 00:59:59 UTC))]
+#[case("20250330T030000", None, datetime!(2025-03-30 01:00:00 UTC))]
+// 02:30 doesn't exist, it is moved past the gap to 03:30 CEST
+#[case("20250330T023000", None, datetime!(2025-03-30 01:30:00 UTC))]
+fn test_parse_time(
+    #[case] value: &str,
+    #[case] tzid: Option<&str>,
+    #[case] expected: OffsetDateTime,
+) {
+    assert_eq!(parse_time(value, tzid), Some(expected));
+}
+
+#[test]
+fn test_parse_time_keeps_local_offset() {
+    let summer = parse_time("20240923T200000", None).unwrap();
+    assert_eq!(summer.offset().whole_hours(), 2);
+    assert_eq!(summer.hour(), 20);
+    let winter = parse_time("20241102T200000", None).unwrap();
+    assert_eq!(winter.offset().whole_hours(), 1);
+    assert_eq!(winter.hour(), 20);
+}
+
+#[test]
+fn test_parse_time_invalid() {
+    assert_eq!(parse_time("2024-11-02 20:00", None), None);
 }
 
 #[test]
 fn test_screenings_selector() {
-    for (path, expected) in [("testdata/play.html", 0), ("testdata/play_curl.html", 14)] {
+    // play.html and play_curl.html were recorded before the website redesign and
+    // no longer match the current selectors.
+    for (path, expected) in [
+        ("testdata/play.html", 0),
+        ("testdata/play_curl.html", 0),
+        ("src/testdata/test_download_play.golden", 1),
+        (
+            "testdata/replay/de/kalender/30546/liebe-einfach-ausserirdisch/index.html",
+            7,
+        ),
+    ] {
         let mut file = File::open(path).unwrap();
         let mut html_content = String::new();
         file.read_to_string(&mut html_content).unwrap();
 
         let fragment = Html::parse_document(&html_content);
         assert_eq!(
-            fragment.select(&SCREENING_SELECTOR).count(),
+            fragment
+                .select(&Schauspielhaus::selectors().screening)
+                .count(),
             expected,
             "path: {}",
             path
@@ -349,9 +3152,52 @@ fn test_screenings_selector() {
 }
 
 #[tokio::test]
-async fn test_get_plays() {
-    let plays = get_plays().await.unwrap();
-    assert_eq!(plays.len(), 23);
+async fn test_plays() {
+    let plays = replay_scraper().plays().await.unwrap();
+    assert_eq!(plays.len(), 3);
+    // only listed in January, on the second calendar page
+    let play = &plays["/de/kalender/32104/der-kirschgarten"];
+    assert_eq!(play.play.name, "Der Kirschgarten");
+    assert_eq!(play.screenings.len(), 1);
+    let play = &plays["/de/kalender/30546/liebe-einfach-ausserirdisch"];
+    assert_eq!(play.play.name, "Liebe, einfach ausserirdisch");
+    assert_eq!(play.screenings.len(), 7);
+    let sold_out = play
+        .screenings
+        .iter()
+        .filter(|s| s.ticket_status == TicketStatus::SoldOut)
+        .map(|s| s.webid.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(sold_out, vec!["event_31438@www.schauspielhaus.ch"]);
+    let tags = play
+        .screenings
+        .iter()
+        .map(|s| s.tags.0.clone())
+        .collect::<Vec<Vec<ScreeningTag>>>();
+    assert_eq!(
+        tags,
+        vec![
+            vec![ScreeningTag::Premiere],
+            vec![],
+            vec![],
+            vec![ScreeningTag::Introduction],
+            vec![ScreeningTag::Talk, ScreeningTag::Surtitles],
+            vec![],
+            vec![ScreeningTag::LastShow],
+        ]
+    );
+    assert_eq!(
+        crate::models::credits_summary(&play.credits).unwrap(),
+        "Regie: René Pollesch / Mit: Sophie Rois, Trystan Pütter, Kotbong Yang"
+    );
+    assert_eq!(play.credits.len(), 8);
+    assert_eq!(play.name(Language::En), "Love, Simply Extraterrestrial");
+    assert!(play
+        .description(Language::En)
+        .starts_with("«Our mission is over???"));
+    // the poetry slam has no English page in the fixtures
+    let poetry_slam = &plays["/de/kalender/31446/poetry-slam-saisoneroeffnung"];
+    assert_eq!(poetry_slam.name(Language::En), poetry_slam.play.name);
 }
 
 #[test]
@@ -362,7 +3208,7 @@ fn test_metainfo_selector() {
     file.read_to_string(&mut html_content).unwrap();
     let fragment = Html::parse_document(&html_content);
     let information = fragment
-        .select(&METAINFO_SELECTOR)
+        .select(&Schauspielhaus::selectors().metainfo)
         .fold("".to_string(), |acc, element| {
             format!("{}\n{}", acc, element.inner_html())
         });
@@ -0,0 +1,159 @@
+use ego_tree::NodeRef;
+use lazy_static::lazy_static;
+use regex::Regex;
+use scraper::Html;
+use scraper::Node;
+use teloxide::utils::markdown;
+use url::Url;
+
+// html_to_markdown converts the HTML of a play description into Telegram
+// MarkdownV2. Paragraphs are separated by an empty line, line breaks, emphasis
+// and links are kept, other tags are left out with their text kept. Relative
+// links are resolved against `base_url`.
+pub fn html_to_markdown(html: &str, base_url: &str) -> String {
+    lazy_static! {
+        static ref EMPTY_LINES: Regex = Regex::new(r"\n{3,}").unwrap();
+    }
+    let fragment = Html::parse_fragment(html);
+    let base_url = Url::parse(base_url).ok();
+    let mut text = String::new();
+    for child in fragment.root_element().children() {
+        convert_node(child, base_url.as_ref(), &mut text);
+    }
+    let text = text
+        .lines()
+        .map(|line| line.trim())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    EMPTY_LINES.replace_all(text.trim(), "\n\n").to_string()
+}
+
+// convert_node appends the MarkdownV2 of the node and its children to `out`.
+// Whitespace is collapsed like a browser would, the lines are trimmed by
+// html_to_markdown.
+fn convert_node(node: NodeRef<Node>, base_url: Option<&Url>, out: &mut String) {
+    let element = match node.value() {
+        Node::Text(text) => {
+            let mut collapsed = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+            if collapsed.is_empty() {
+                if !text.is_empty() && !out.ends_with([' ', '\n']) {
+                    out.push(' ');
+                }
+                return;
+            }
+            if text.starts_with(char::is_whitespace) && !out.ends_with([' ', '\n']) {
+                out.push(' ');
+            }
+            if text.ends_with(char::is_whitespace) {
+                collapsed.push(' ');
+            }
+            out.push_str(&markdown::escape(&collapsed));
+            return;
+        }
+        Node::Element(element) => element,
+        _ => return,
+    };
+    let children = |out: &mut String| {
+        let mut inner = String::new();
+        for child in node.children() {
+            convert_node(child, base_url, &mut inner);
+        }
+        out.push_str(&inner);
+    };
+    match element.name() {
+        "br" => out.push('\n'),
+        "script" | "style" => {}
+        "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "ul" | "ol" | "blockquote" => {
+            out.push_str("\n\n");
+            children(out);
+            out.push_str("\n\n");
+        }
+        "li" => {
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str("• ");
+            children(out);
+            out.push('\n');
+        }
+        "em" | "i" => wrap(node, base_url, "_", "_", out),
+        "strong" | "b" => wrap(node, base_url, "*", "*", out),
+        "u" => wrap(node, base_url, "__", "__", out),
+        "s" | "del" | "strike" => wrap(node, base_url, "~", "~", out),
+        "a" => {
+            let url = element
+                .attr("href")
+                .and_then(|href| match base_url {
+                    Some(base) => base.join(href).ok(),
+                    None => Url::parse(href).ok(),
+                })
+                .filter(|url| ["http", "https", "mailto"].contains(&url.scheme()));
+            match url {
+                Some(url) => {
+                    let end = format!("]({})", markdown::escape_link_url(url.as_str()));
+                    wrap(node, base_url, "[", &end, out)
+                }
+                None => children(out),
+            }
+        }
+        _ => children(out),
+    }
+}
+
+// wrap appends the children of the node between `start` and `end`. Whitespace
+// at the edges is moved outside, Telegram doesn't accept entities that start
+// or end with a space, and empty entities are left out.
+fn wrap(node: NodeRef<Node>, base_url: Option<&Url>, start: &str, end: &str, out: &mut String) {
+    let mut inner = String::new();
+    for child in node.children() {
+        convert_node(child, base_url, &mut inner);
+    }
+    let trimmed = inner.trim();
+    if trimmed.is_empty() {
+        out.push_str(&inner);
+        return;
+    }
+    if inner.starts_with(char::is_whitespace) && !out.ends_with([' ', '\n']) {
+        out.push(' ');
+    }
+    out.push_str(start);
+    out.push_str(trimmed);
+    out.push_str(end);
+    if inner.ends_with('\n') {
+        out.push('\n');
+    } else if inner.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+#[cfg(test)]
+use rstest::rstest;
+
+#[cfg(test)]
+#[rstest]
+#[case::paragraphs(
+    "<p>Ein Abend.</p>\n\n<p>Zwei  Stunden.</p>",
+    "Ein Abend\\.\n\nZwei Stunden\\."
+)]
+#[case::emphasis(
+    "<p>Mit <em>LIEBE </em>und <strong>Glück</strong>!</p>",
+    "Mit _LIEBE_ und *Glück*\\!"
+)]
+#[case::empty_emphasis("<p>Ein<em> </em>Abend</p>", "Ein Abend")]
+#[case::line_breaks("<p><em>MIT</em><br />\n•&nbsp; A<br />\n&nbsp;</p>", "_MIT_\n• A")]
+#[case::relative_link(
+    "<p>Im <a href=\"/de/spielorte\">Pfauen</a>.</p>",
+    "Im [Pfauen](https://www.schauspielhaus.ch/de/spielorte)\\."
+)]
+#[case::link_with_parenthesis(
+    "<a href=\"https://example.com/a_(b)\">a_b</a>",
+    "[a\\_b](https://example.com/a_(b\\))"
+)]
+#[case::script_link("<a href=\"javascript:alert(1)\">Klick</a>", "Klick")]
+#[case::list("<ul><li>Eins</li><li>Zwei</li></ul>", "• Eins\n• Zwei")]
+fn test_html_to_markdown(#[case] html: &str, #[case] expected: &str) {
+    assert_eq!(
+        html_to_markdown(html, "https://www.schauspielhaus.ch"),
+        expected
+    );
+}